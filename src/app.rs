@@ -1,38 +1,149 @@
-use crate::binary_numbers::{BinaryNumbersGame, Bits};
+use crate::binary_numbers::{
+    AnswerMode, Bits, Conversion, CustomBits, DistractorStrategy, GameMode, Lives, PuzzleConfig,
+    PuzzleKind, new_game, step_wrapping,
+};
+use crate::daily;
+use crate::fixed_point::q_format;
+use crate::flashcards;
 use crate::keybinds;
 use crate::main_screen_widget::MainScreenWidget;
-use crate::utils::ProceduralAnimationWidget;
+use crate::mixed;
+use crate::utils::{ProceduralAnimationWidget, config_dir};
 use crossterm::event;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use indoc::indoc;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::prelude::{Color, Modifier, Span, Style};
-use ratatui::widgets::{List, ListItem, ListState};
+use ratatui::prelude::{Color, Line, Modifier, Span, Style, Widget};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
 use std::cmp;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
 use std::thread;
 use std::time::{Duration, Instant};
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum NumberMode {
     Unsigned,
+    /// Two's complement
     Signed,
+    /// The top bit is the sign, the others the magnitude
+    SignMagnitude,
+    /// Negative numbers have all bits of the magnitude flipped
+    OnesComplement,
+    /// Offset binary, the stored pattern is the value plus a bias like in float exponents
+    Excess,
 }
 
 impl NumberMode {
+    pub const ALL: [Self; 5] =
+        [Self::Unsigned, Self::Signed, Self::SignMagnitude, Self::OnesComplement, Self::Excess];
+
     pub const fn label(&self) -> &'static str {
         match self {
             Self::Unsigned => "UNSIGNED",
             Self::Signed => "SIGNED",
+            Self::SignMagnitude => "SIGN-MAG",
+            Self::OnesComplement => "1S-COMPL",
+            Self::Excess => "EXCESS-K",
+        }
+    }
+
+    /// Like `label`, but with the bias of the given width filled in, e.g. "EXCESS-127"
+    pub fn label_for(self, num_bits: u32) -> String {
+        match self {
+            Self::Excess => format!("EXCESS-{}", Self::bias(num_bits)),
+            _ => self.label().to_string(),
+        }
+    }
+
+    pub const fn high_score_suffix(&self) -> &'static str {
+        match self {
+            Self::Unsigned => "u",
+            Self::Signed => "s",
+            Self::SignMagnitude => "sm",
+            Self::OnesComplement => "oc",
+            Self::Excess => "ex",
+        }
+    }
+
+    pub const fn is_signed(self) -> bool {
+        !matches!(self, Self::Unsigned)
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Bias of excess-K for the given width, 2^(n-1) - 1 like IEEE 754 exponents
+    pub const fn bias(num_bits: u32) -> i64 {
+        (1 << (num_bits - 1)) - 1
+    }
+
+    /// How many different values the patterns of the given width stand for.
+    /// Sign-magnitude and ones' complement spend two patterns on zero.
+    pub const fn distinct_values(self, num_bits: u32) -> u64 {
+        match self {
+            Self::SignMagnitude | Self::OnesComplement => (1 << num_bits) - 1,
+            _ => 1 << num_bits,
+        }
+    }
+
+    /// Decimal value of a raw bit pattern of the given width
+    pub const fn value_of(self, raw: u64, num_bits: u32) -> i64 {
+        let sign_bit = 1 << (num_bits - 1);
+        match self {
+            Self::Unsigned => raw as i64,
+            // Negative number: raw - 2^n
+            Self::Signed if raw >= sign_bit => raw as i64 - (1 << num_bits),
+            Self::SignMagnitude if raw >= sign_bit => -((raw - sign_bit) as i64),
+            // Negative number: raw - (2^n - 1)
+            Self::OnesComplement if raw >= sign_bit => raw as i64 - ((1 << num_bits) - 1),
+            Self::Signed | Self::SignMagnitude | Self::OnesComplement => raw as i64,
+            Self::Excess => raw as i64 - Self::bias(num_bits),
+        }
+    }
+
+    /// Raw bit pattern of a decimal value, the inverse of `value_of`.
+    /// Zero is always encoded as all zeros, never as negative zero.
+    pub const fn raw_of(self, value: i64, num_bits: u32) -> u64 {
+        let mask = (1u64 << num_bits) - 1;
+        let magnitude = value.unsigned_abs();
+        match self {
+            Self::SignMagnitude if value < 0 => 1 << (num_bits - 1) | magnitude,
+            Self::OnesComplement if value < 0 => !magnitude & mask,
+            Self::Excess => (value + Self::bias(num_bits)) as u64 & mask,
+            _ => value as u64 & mask,
         }
     }
 }
 
-/// Persistent application preferences that survive across menu/game transitions
-#[derive(Copy, Clone, Debug)]
+/// Persistent application preferences that survive across menu/game transitions,
+/// and across launches in the preferences file
+#[derive(Copy, Clone, PartialEq, Debug)]
 struct AppPreferences {
     last_selected_index: usize,
     last_number_mode: NumberMode,
+    last_puzzle_kind: PuzzleKind,
+    last_conversion: Conversion,
+    last_answer_mode: AnswerMode,
+    last_distractors: DistractorStrategy,
+    last_game_mode: GameMode,
+    last_lives: Lives,
+    last_custom_bits: CustomBits,
+    last_fraction_bits: Option<u32>,
+    /// The start menu animation was stopped with A
+    animation_paused: bool,
+    /// From `--seed`, every game started from the menu replays the same numbers.
+    /// Only ever given on the command line, so it is never saved.
+    seed: Option<u64>,
 }
 
 impl Default for AppPreferences {
@@ -40,6 +151,174 @@ impl Default for AppPreferences {
         Self {
             last_selected_index: 4, // Default to "byte 8 bit"
             last_number_mode: NumberMode::Unsigned,
+            last_puzzle_kind: PuzzleKind::default(),
+            last_conversion: Conversion::default(),
+            last_answer_mode: AnswerMode::default(),
+            last_distractors: DistractorStrategy::default(),
+            last_game_mode: GameMode::default(),
+            last_lives: Lives::default(),
+            last_custom_bits: CustomBits::default(),
+            last_fraction_bits: None, // half of the width, like Q8.8
+            animation_paused: false,
+            seed: None,
+        }
+    }
+}
+
+impl AppPreferences {
+    const FILE: &'static str = "preferences.txt";
+
+    /// `binbreak/preferences.txt` in the config directory of the platform
+    fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("binbreak").join(Self::FILE))
+    }
+
+    /// The saved preferences, the defaults if there are none or they can't be read
+    fn load(seed: Option<u64>) -> Self {
+        let contents = Self::path().and_then(|path| fs::read_to_string(path).ok());
+        Self { seed, ..Self::parse(&contents.unwrap_or_default()) }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let path = Self::path().ok_or(std::io::ErrorKind::NotFound)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_text())
+    }
+
+    /// Lines like `number_mode=SIGNED` with the labels shown in the menu.
+    /// Unknown or broken lines keep the default of their setting.
+    fn parse(contents: &str) -> Self {
+        let mut prefs = Self::default();
+        for line in contents.lines() {
+            let Some((name, value)) = line.split_once('=') else { continue };
+            let value = value.trim();
+            match name.trim() {
+                "selected" => {
+                    if let Ok(index) = value.parse() {
+                        prefs.last_selected_index = index;
+                    }
+                },
+                "number_mode" => {
+                    if let Some(mode) = NumberMode::ALL.into_iter().find(|m| m.label() == value) {
+                        prefs.last_number_mode = mode;
+                    }
+                },
+                "puzzle" => {
+                    if let Some(kind) = by_label(value, PuzzleKind::next, PuzzleKind::label) {
+                        prefs.last_puzzle_kind = kind;
+                    }
+                },
+                "drill" => {
+                    if let Some(conversion) =
+                        Conversion::ALL.into_iter().find(|c| c.label() == value)
+                    {
+                        prefs.last_conversion = conversion;
+                    }
+                },
+                "answer" => {
+                    if let Some(mode) = by_label(value, AnswerMode::next, AnswerMode::label) {
+                        prefs.last_answer_mode = mode;
+                    }
+                },
+                "decoys" => {
+                    if let Some(strategy) =
+                        by_label(value, DistractorStrategy::next, DistractorStrategy::label)
+                    {
+                        prefs.last_distractors = strategy;
+                    }
+                },
+                "game" => {
+                    if let Some(mode) = by_label(value, GameMode::next, GameMode::label) {
+                        prefs.last_game_mode = mode;
+                    }
+                },
+                "lives" => {
+                    if let Some(lives) = by_label(value, Lives::next, Lives::label) {
+                        prefs.last_lives = lives;
+                    }
+                },
+                "custom" => {
+                    let numbers: Vec<&str> = value.split_whitespace().collect();
+                    if let [width, shift, suggestions] = numbers[..]
+                        && let (Ok(width), Ok(shift), Ok(suggestions)) =
+                            (width.parse(), shift.parse(), suggestions.parse())
+                        && let Some(custom) = CustomBits::new(width, shift, suggestions)
+                    {
+                        prefs.last_custom_bits = custom;
+                    }
+                },
+                "fraction_bits" => prefs.last_fraction_bits = value.parse().ok(),
+                "animation" => prefs.animation_paused = value == "paused",
+                _ => {},
+            }
+        }
+        prefs
+    }
+
+    fn to_text(self) -> String {
+        let custom = self.last_custom_bits;
+        let mut data = String::new();
+        let _ = writeln!(data, "selected={}", self.last_selected_index);
+        let _ = writeln!(data, "number_mode={}", self.last_number_mode.label());
+        let _ = writeln!(data, "puzzle={}", self.last_puzzle_kind.label());
+        let _ = writeln!(data, "drill={}", self.last_conversion.label());
+        let _ = writeln!(data, "answer={}", self.last_answer_mode.label());
+        let _ = writeln!(data, "decoys={}", self.last_distractors.label());
+        let _ = writeln!(data, "game={}", self.last_game_mode.label());
+        let _ = writeln!(data, "lives={}", self.last_lives.label());
+        let _ = writeln!(data, "custom={} {} {}", custom.width, custom.shift, custom.suggestions);
+        if let Some(fraction_bits) = self.last_fraction_bits {
+            let _ = writeln!(data, "fraction_bits={fraction_bits}");
+        }
+        let animation = if self.animation_paused { "paused" } else { "running" };
+        let _ = writeln!(data, "animation={animation}");
+        data
+    }
+}
+
+/// The setting with the given label, found by cycling through all of them from the default
+fn by_label<T: Copy + PartialEq + Default>(
+    label: &str,
+    next: fn(T) -> T,
+    label_of: fn(&T) -> &'static str,
+) -> Option<T> {
+    let first = T::default();
+    std::iter::successors(Some(first), |&value| Some(next(value)).filter(|&value| value != first))
+        .find(|value| label_of(value) == label)
+}
+
+/// What a start menu entry starts
+#[derive(Clone)]
+enum MenuEntry {
+    /// A game of this width, with the settings below the menu
+    Bits(Bits),
+    /// A width and number mode of its own for every round
+    Mixed,
+    /// The flashcards of the study deck that are due today
+    Study,
+    /// Today's daily challenge, the same for everyone
+    Daily,
+}
+
+impl MenuEntry {
+    fn bits(&self) -> Bits {
+        match self {
+            Self::Bits(bits) => bits.clone(),
+            // the width of the first rounds, later ones get wider
+            Self::Mixed => Bits::Four,
+            // the first new cards are nibbles
+            Self::Study => Bits::Four,
+            Self::Daily => daily::BITS,
+        }
+    }
+    fn color(&self) -> Color {
+        match self {
+            Self::Bits(bits) => get_mode_color(bits),
+            Self::Mixed => Color::Rgb(230, 230, 230), // white, all colors mixed
+            Self::Study => Color::Rgb(170, 255, 210), // mint
+            Self::Daily => Color::Rgb(255, 240, 170), // sand
         }
     }
 }
@@ -53,8 +332,12 @@ pub fn get_mode_color(bits: &Bits) -> Color {
         Bits::FourShift8 => Color::Rgb(100, 220, 255),  // light blue
         Bits::FourShift12 => Color::Rgb(100, 180, 255), // blue
         Bits::Eight => Color::Rgb(150, 120, 255),       // royal blue
+        Bits::Nine => Color::Rgb(175, 110, 255),        // violet
         Bits::Twelve => Color::Rgb(200, 100, 255),      // purple
         Bits::Sixteen => Color::Rgb(255, 80, 150),      // pink
+        Bits::TwentyFour => Color::Rgb(255, 80, 100),   // rose
+        Bits::ThirtyTwo => Color::Rgb(255, 70, 60),     // red
+        Bits::Custom(_) => Color::Rgb(255, 190, 60),    // amber
     }
 }
 
@@ -66,7 +349,7 @@ enum FpsMode {
 
 enum AppState {
     Start(StartMenuState, AppPreferences),
-    Playing(BinaryNumbersGame, AppPreferences),
+    Playing(Box<dyn MainScreenWidget>, AppPreferences),
     Exit,
 }
 
@@ -78,22 +361,44 @@ fn handle_start_input(
     match key {
         x if keybinds::is_up(x) => state.select_previous(),
         x if keybinds::is_down(x) => state.select_next(),
-        x if keybinds::is_left(x) | keybinds::is_right(x) => state.toggle_number_mode(),
+        x if keybinds::is_left(x) => state.step_number_mode(false),
+        x if keybinds::is_right(x) => state.step_number_mode(true),
         x if keybinds::is_select(x) => {
-            let bits = state.selected_bits();
-            let number_mode = state.number_mode;
-            // Update preferences with current selection
-            let updated_prefs = AppPreferences {
-                last_selected_index: state.selected_index(),
-                last_number_mode: state.number_mode,
+            let config = match state.selected_entry() {
+                MenuEntry::Bits(bits) => {
+                    PuzzleConfig::new(bits.clone(), state.selected_number_mode())
+                        .with_kind(state.selected_puzzle_kind())
+                        .with_conversion(state.selected_conversion())
+                        .with_answer_mode(state.answer_mode)
+                        .with_distractors(state.distractors)
+                        .with_fraction_bits(state.selected_fraction_bits())
+                        .with_seed(prefs.seed)
+                        .with_game_mode(state.game_mode)
+                        .with_lives(state.lives)
+                },
+                MenuEntry::Mixed => mixed::config(prefs.seed),
+                MenuEntry::Study => flashcards::config(),
+                MenuEntry::Daily => daily::config(daily::today()),
             };
-            return Some((
-                AppState::Playing(BinaryNumbersGame::new(bits, number_mode), updated_prefs),
-                updated_prefs,
-            ));
+            // Update preferences with current selection
+            let updated_prefs = state.preferences(prefs.seed);
+            return Some((AppState::Playing(new_game(config), updated_prefs), updated_prefs));
         },
-        x if keybinds::is_exit(x) => return Some((AppState::Exit, prefs)),
+        x if keybinds::is_exit(x) => return Some((AppState::Exit, state.preferences(prefs.seed))),
         KeyEvent { code: KeyCode::Char('a' | 'A'), .. } => state.toggle_animation(),
+        KeyEvent { code: KeyCode::Char('d' | 'D'), .. } => state.cycle_conversion(),
+        KeyEvent { code: KeyCode::Char('e' | 'E'), .. } => state.cycle_answer_mode(),
+        KeyEvent { code: KeyCode::Char('p' | 'P'), .. } => state.cycle_puzzle_kind(),
+        KeyEvent { code: KeyCode::Char('g' | 'G'), .. } => state.cycle_game_mode(),
+        KeyEvent { code: KeyCode::Char('t' | 'T'), .. } => state.cycle_distractors(),
+        KeyEvent { code: KeyCode::Char('v' | 'V'), .. } => state.cycle_lives(),
+        KeyEvent { code: KeyCode::Char(c @ ('f' | 'F')), .. } => {
+            state.step_fraction_bits(c == 'f');
+        },
+        // custom width settings, Shift steps backwards
+        KeyEvent { code: KeyCode::Char(c @ ('w' | 'W' | 's' | 'S' | 'c' | 'C')), .. } => {
+            state.adjust_custom_bits(c);
+        },
         _ => {},
     }
     None
@@ -116,9 +421,14 @@ fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer)
     #[allow(clippy::cast_possible_truncation)]
     let list_height = upper_labels.len() as u16;
 
-    // Vertical spacing between ASCII art and list
+    let settings = settings_lines(state);
+    #[allow(clippy::cast_possible_truncation)]
+    let settings_height = settings.len() as u16;
+
+    // Vertical spacing between ASCII art and list, and between list and settings
     let spacing: u16 = 3;
-    let total_height = ascii_height + spacing + list_height;
+    let settings_spacing: u16 = 1;
+    let total_height = ascii_height + spacing + list_height + settings_spacing + settings_height;
 
     // Center vertically & horizontally
     let start_y = area.y + area.height.saturating_sub(total_height) / 2;
@@ -138,7 +448,7 @@ fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer)
     );
 
     // Get color for the selected menu item
-    let selected_color = get_mode_color(&state.items[selected].1);
+    let selected_color = state.items[selected].1.color();
 
     // Update animation color to match selected menu item
     state.animation.set_highlight_color(selected_color);
@@ -156,14 +466,20 @@ fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer)
 
             // Add number mode for selected item
             let mode_display = if is_selected {
-                format!("{:>width$}", state.number_mode.label(), width = mode_label_width as usize)
+                let number_mode = match state.items[i].1 {
+                    MenuEntry::Bits(_) => state.selected_number_mode().label(),
+                    MenuEntry::Mixed => "RANDOM",
+                    MenuEntry::Study => NumberMode::Unsigned.label(),
+                    MenuEntry::Daily => daily::NUMBER_MODE.label(),
+                };
+                format!("{:>width$}", number_mode, width = mode_label_width as usize)
             } else {
                 " ".repeat(mode_label_width as usize)
             };
 
             let line = format!("{marker} {padded_label}    {mode_display}");
 
-            let item_color = get_mode_color(&state.items[i].1);
+            let item_color = state.items[i].1.color();
             let mut style = Style::default().fg(item_color).add_modifier(Modifier::BOLD);
 
             // Make selected item extra prominent with background highlight
@@ -177,6 +493,58 @@ fn render_start_screen(state: &mut StartMenuState, area: Rect, buf: &mut Buffer)
 
     let list = List::new(items);
     ratatui::widgets::StatefulWidget::render(list, list_area, buf, &mut state.list_state);
+
+    let settings_y = list_y + list_height + settings_spacing;
+    let settings_area =
+        Rect::new(list_x, settings_y, list_width, settings_height).intersection(area);
+    Paragraph::new(settings).render(settings_area, buf);
+}
+
+/// One line per start menu setting, with the hotkey that changes it
+fn settings_lines(state: &StartMenuState) -> Vec<Line<'static>> {
+    let rules = match state.selected_entry() {
+        MenuEntry::Bits(_) => None,
+        MenuEntry::Mixed => Some("a new width every round".to_string()),
+        MenuEntry::Study => Some("flashcards, missed ones come back".to_string()),
+        MenuEntry::Daily => Some(format!("{} rounds, one scored try a day", daily::ROUNDS)),
+    };
+    if let Some(rules) = rules {
+        return vec![Line::from(Span::styled(rules, Style::default().fg(Color::DarkGray)))];
+    }
+    let mut settings = vec![
+        ("g", "game", state.game_mode.label().to_string()),
+        ("p", "puzzle", state.selected_puzzle_kind().label().to_string()),
+        ("d", "drill", state.selected_conversion().label().to_string()),
+        ("e", "answer", state.answer_mode.label().to_string()),
+        ("t", "decoys", state.distractors.label().to_string()),
+    ];
+    if state.game_mode.has_lives() {
+        settings.insert(1, ("v", "lives", state.lives.label().to_string()));
+    }
+    if state.selected_puzzle_kind() == PuzzleKind::FixedPoint {
+        let width = state.selected_bits().to_int();
+        settings.push(("f/F", "point", q_format(width, state.selected_fraction_bits())));
+    }
+    if let Bits::Custom(custom) = state.selected_bits() {
+        settings.extend([
+            ("w/W", "width", format!("{} bit", custom.width)),
+            ("s/S", "shift", format!("x{}", 1u64 << custom.shift)),
+            ("c/C", "choices", custom.suggestions.to_string()),
+        ]);
+    }
+    settings
+        .into_iter()
+        .map(|(key, name, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<6}", format!("<{key}>")),
+                    Style::default().fg(Color::LightCyan),
+                ),
+                Span::styled(format!("{name:<8}"), Style::default().fg(Color::DarkGray)),
+                Span::styled(value, Style::default().fg(Color::White)),
+            ])
+        })
+        .collect()
 }
 
 fn handle_crossterm_events(app_state: &mut AppState) -> color_eyre::Result<()> {
@@ -186,6 +554,9 @@ fn handle_crossterm_events(app_state: &mut AppState) -> color_eyre::Result<()> {
         match key.code {
             // global exit via Ctrl+C
             KeyCode::Char('c' | 'C') if key.modifiers == KeyModifiers::CONTROL => {
+                if let AppState::Start(menu, prefs) = app_state {
+                    let _ = menu.preferences(prefs.seed).save();
+                }
                 *app_state = AppState::Exit;
             },
 
@@ -193,7 +564,10 @@ fn handle_crossterm_events(app_state: &mut AppState) -> color_eyre::Result<()> {
             _ => {
                 *app_state = match std::mem::replace(app_state, AppState::Exit) {
                     AppState::Start(mut menu, prefs) => {
-                        if let Some((new_state, _)) = handle_start_input(&mut menu, key, prefs) {
+                        if let Some((new_state, prefs)) = handle_start_input(&mut menu, key, prefs)
+                        {
+                            // leaving the menu, the next launch starts where this one left off
+                            let _ = prefs.save();
                             new_state
                         } else {
                             AppState::Start(menu, prefs)
@@ -212,7 +586,7 @@ fn handle_crossterm_events(app_state: &mut AppState) -> color_eyre::Result<()> {
 }
 
 /// Determine the appropriate FPS mode based on the current game state
-fn get_fps_mode(game: &BinaryNumbersGame) -> FpsMode {
+fn get_fps_mode(game: &dyn MainScreenWidget) -> FpsMode {
     if game.is_active() {
         FpsMode::RealTime // Timer running, needs continuous updates
     } else {
@@ -220,8 +594,11 @@ fn get_fps_mode(game: &BinaryNumbersGame) -> FpsMode {
     }
 }
 
-pub fn run_app(terminal: &mut ratatui::DefaultTerminal) -> color_eyre::Result<()> {
-    let prefs = AppPreferences::default();
+pub fn run_app(
+    terminal: &mut ratatui::DefaultTerminal,
+    seed: Option<u64>,
+) -> color_eyre::Result<()> {
+    let prefs = AppPreferences::load(seed);
     let mut app_state = AppState::Start(StartMenuState::new(prefs), prefs);
     let mut last_frame_time = Instant::now();
     let target_frame_duration = std::time::Duration::from_millis(33); // ~30 FPS
@@ -242,13 +619,13 @@ pub fn run_app(terminal: &mut ratatui::DefaultTerminal) -> color_eyre::Result<()
 
         terminal.draw(|f| match &mut app_state {
             AppState::Start(menu, _) => render_start_screen(menu, f.area(), f.buffer_mut()),
-            AppState::Playing(game, _) => f.render_widget(&mut *game, f.area()),
+            AppState::Playing(game, _) => game.render_ref(f.area(), f.buffer_mut()),
             AppState::Exit => {},
         })?;
 
         // handle input
         if let AppState::Playing(game, _) = &app_state {
-            if get_fps_mode(game) == FpsMode::RealTime {
+            if get_fps_mode(game.as_ref()) == FpsMode::RealTime {
                 let poll_timeout = cmp::min(dt, target_frame_duration);
                 if event::poll(poll_timeout)? {
                     handle_crossterm_events(&mut app_state)?;
@@ -361,10 +738,17 @@ fn ascii_animation() -> ProceduralAnimationWidget {
 
 // Start menu state
 struct StartMenuState {
-    items: Vec<(String, Bits)>,
+    items: Vec<(String, MenuEntry)>,
     list_state: ListState,
     animation: ProceduralAnimationWidget,
     number_mode: NumberMode,
+    puzzle_kind: PuzzleKind,
+    conversion: Conversion,
+    answer_mode: AnswerMode,
+    distractors: DistractorStrategy,
+    game_mode: GameMode,
+    lives: Lives,
+    fraction_bits: Option<u32>,
 }
 
 impl StartMenuState {
@@ -374,28 +758,69 @@ impl StartMenuState {
 
     fn with_preferences(prefs: AppPreferences) -> Self {
         let items = vec![
-            ("nibble_0    4 bit".to_string(), Bits::Four),
-            ("nibble_1    4 bit*16".to_string(), Bits::FourShift4),
-            ("nibble_2    4 bit*256".to_string(), Bits::FourShift8),
-            ("nibble_3    4 bit*4096".to_string(), Bits::FourShift12),
-            ("byte        8 bit".to_string(), Bits::Eight),
-            ("hexlet     12 bit".to_string(), Bits::Twelve),
-            ("word       16 bit".to_string(), Bits::Sixteen),
+            ("nibble_0    4 bit".to_string(), MenuEntry::Bits(Bits::Four)),
+            ("nibble_1    4 bit*16".to_string(), MenuEntry::Bits(Bits::FourShift4)),
+            ("nibble_2    4 bit*256".to_string(), MenuEntry::Bits(Bits::FourShift8)),
+            ("nibble_3    4 bit*4096".to_string(), MenuEntry::Bits(Bits::FourShift12)),
+            ("byte        8 bit".to_string(), MenuEntry::Bits(Bits::Eight)),
+            ("perms       9 bit".to_string(), MenuEntry::Bits(Bits::Nine)),
+            ("hexlet     12 bit".to_string(), MenuEntry::Bits(Bits::Twelve)),
+            ("word       16 bit".to_string(), MenuEntry::Bits(Bits::Sixteen)),
+            ("triple     24 bit".to_string(), MenuEntry::Bits(Bits::TwentyFour)),
+            ("dword      32 bit".to_string(), MenuEntry::Bits(Bits::ThirtyTwo)),
+            custom_item(prefs.last_custom_bits),
+            ("mixed    4-32 bit".to_string(), MenuEntry::Mixed),
+            (format!("study {:>7} due", flashcards::due_today()), MenuEntry::Study),
+            (format!("daily {}", daily::date_of_day(daily::today())), MenuEntry::Daily),
         ];
 
+        // the preferences file may come from a version with a different menu
+        let selected = prefs.last_selected_index.min(items.len() - 1);
+        let mut animation = ascii_animation();
+        if prefs.animation_paused {
+            animation.toggle_pause();
+        }
         Self {
             items,
-            list_state: ListState::default().with_selected(Some(prefs.last_selected_index)),
-            animation: ascii_animation(),
+            list_state: ListState::default().with_selected(Some(selected)),
+            animation,
             number_mode: prefs.last_number_mode,
+            puzzle_kind: prefs.last_puzzle_kind,
+            conversion: prefs.last_conversion,
+            answer_mode: prefs.last_answer_mode,
+            distractors: prefs.last_distractors,
+            game_mode: prefs.last_game_mode,
+            lives: prefs.last_lives,
+            fraction_bits: prefs.last_fraction_bits,
+        }
+    }
+
+    /// The current selection and settings, to start the menu with next time
+    fn preferences(&self, seed: Option<u64>) -> AppPreferences {
+        AppPreferences {
+            last_selected_index: self.selected_index(),
+            last_number_mode: self.number_mode,
+            last_puzzle_kind: self.puzzle_kind,
+            last_conversion: self.conversion,
+            last_answer_mode: self.answer_mode,
+            last_distractors: self.distractors,
+            last_game_mode: self.game_mode,
+            last_lives: self.lives,
+            last_custom_bits: self.custom_bits(),
+            last_fraction_bits: self.fraction_bits,
+            animation_paused: self.animation.is_paused(),
+            seed,
         }
     }
 
     fn selected_index(&self) -> usize {
         self.list_state.selected().unwrap_or(0)
     }
+    fn selected_entry(&self) -> &MenuEntry {
+        &self.items[self.selected_index()].1
+    }
     fn selected_bits(&self) -> Bits {
-        self.items[self.selected_index()].1.clone()
+        self.selected_entry().bits()
     }
     fn select_next(&mut self) {
         let current = self.selected_index();
@@ -418,10 +843,147 @@ impl StartMenuState {
     fn toggle_animation(&mut self) {
         self.animation.toggle_pause();
     }
-    fn toggle_number_mode(&mut self) {
-        self.number_mode = match self.number_mode {
-            NumberMode::Unsigned => NumberMode::Signed,
-            NumberMode::Signed => NumberMode::Unsigned,
+    /// The chosen number mode, or unsigned if the selected puzzles can't tell them apart
+    fn selected_number_mode(&self) -> NumberMode {
+        if self.selected_puzzle_kind().supports(self.number_mode) {
+            self.number_mode
+        } else {
+            NumberMode::Unsigned
+        }
+    }
+    fn step_number_mode(&mut self, forward: bool) {
+        let kind = self.selected_puzzle_kind();
+        let step = |mode: NumberMode| if forward { mode.next() } else { mode.previous() };
+        let mut next = step(self.selected_number_mode());
+        while !kind.supports(next) {
+            next = step(next);
+        }
+        self.number_mode = next;
+    }
+    /// The chosen drill, or the classic one if it doesn't exist for the selected width
+    fn selected_conversion(&self) -> Conversion {
+        if self.conversion.is_available_for(&self.selected_bits()) {
+            self.conversion
+        } else {
+            Conversion::default()
+        }
+    }
+    fn cycle_conversion(&mut self) {
+        let bits = self.selected_bits();
+        let mut next = self.selected_conversion().next();
+        while !next.is_available_for(&bits) {
+            next = next.next();
+        }
+        self.conversion = next;
+    }
+    fn cycle_answer_mode(&mut self) {
+        self.answer_mode = self.answer_mode.next();
+    }
+    fn cycle_distractors(&mut self) {
+        self.distractors = self.distractors.next();
+    }
+    fn cycle_game_mode(&mut self) {
+        self.game_mode = self.game_mode.next();
+    }
+    fn cycle_lives(&mut self) {
+        self.lives = self.lives.next();
+    }
+    /// The chosen puzzle kind, or conversion if it doesn't exist for the selected width
+    fn selected_puzzle_kind(&self) -> PuzzleKind {
+        if self.puzzle_kind.is_available_for(&self.selected_bits()) {
+            self.puzzle_kind
+        } else {
+            PuzzleKind::default()
+        }
+    }
+    fn cycle_puzzle_kind(&mut self) {
+        let bits = self.selected_bits();
+        let mut next = self.selected_puzzle_kind().next();
+        while !next.is_available_for(&bits) {
+            next = next.next();
+        }
+        self.puzzle_kind = next;
+    }
+    /// Bits after the binary point, at least one on either side of it
+    fn selected_fraction_bits(&self) -> u32 {
+        let width = self.selected_bits().to_int();
+        self.fraction_bits.map_or(width / 2, |bits| bits.clamp(1, width.saturating_sub(1).max(1)))
+    }
+    fn step_fraction_bits(&mut self, forward: bool) {
+        let width = self.selected_bits().to_int();
+        if self.selected_puzzle_kind() == PuzzleKind::FixedPoint {
+            let current = self.selected_fraction_bits();
+            self.fraction_bits = Some(step_wrapping(current, 1, width - 1, forward));
+        }
+    }
+    /// Settings of the custom entry, kept while other entries are selected
+    fn custom_bits(&self) -> CustomBits {
+        self.items
+            .iter()
+            .find_map(|(_, entry)| match entry {
+                MenuEntry::Bits(Bits::Custom(custom)) => Some(*custom),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+    /// Only has an effect while the custom entry is selected
+    fn adjust_custom_bits(&mut self, key: char) {
+        let index = self.selected_index();
+        let MenuEntry::Bits(Bits::Custom(custom)) = self.items[index].1 else {
+            return;
         };
+        let forward = key.is_ascii_lowercase();
+        let custom = match key.to_ascii_lowercase() {
+            'w' => custom.step_width(forward),
+            's' => custom.step_shift(forward),
+            _ => custom.step_suggestions(forward),
+        };
+        self.items[index] = custom_item(custom);
+    }
+}
+
+/// The custom menu entry, labelled with its current width so it lines up with the presets
+fn custom_item(custom: CustomBits) -> (String, MenuEntry) {
+    let bits = Bits::Custom(custom);
+    let padding = if custom.width < 10 { " " } else { "" };
+    (format!("custom     {padding}{}", bits.label()), MenuEntry::Bits(bits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preferences_survive_a_save_and_load_without_the_seed() {
+        let prefs = AppPreferences {
+            last_selected_index: 7,
+            last_number_mode: NumberMode::OnesComplement,
+            last_puzzle_kind: PuzzleKind::FixedPoint,
+            last_conversion: Conversion::GrayToDecimal,
+            last_answer_mode: AnswerMode::FreeEntry,
+            last_distractors: DistractorStrategy::Tricky,
+            last_game_mode: GameMode::Zen,
+            last_lives: Lives::Endless,
+            last_custom_bits: CustomBits { width: 5, shift: 3, suggestions: 2 },
+            last_fraction_bits: Some(3),
+            animation_paused: true,
+            seed: Some(42),
+        };
+        let text = prefs.to_text();
+        assert!(text.contains("drill=GRAY → DEC\n"), "{text}");
+        assert!(!text.contains("42"), "{text}");
+        assert_eq!(AppPreferences::parse(&text), AppPreferences { seed: None, ..prefs });
+    }
+
+    #[test]
+    fn broken_preferences_fall_back_to_the_defaults() {
+        assert_eq!(AppPreferences::parse(""), AppPreferences::default());
+        let prefs = AppPreferences::parse(
+            "selected=-1\nnumber_mode=SIGNED\ngame=ZEN MASTER\ncustom=40 0 4\nlives\n\u{0}",
+        );
+        assert_eq!(
+            prefs,
+            AppPreferences { last_number_mode: NumberMode::Signed, ..AppPreferences::default() }
+        );
     }
 }
@@ -0,0 +1,368 @@
+use crate::app::NumberMode;
+use crate::binary_numbers::{PuzzleConfig, grouped_binary};
+use crate::keybinds;
+use crate::main_screen_widget::WidgetRef;
+use crate::puzzle::{
+    Choices, GuessResult, Puzzle, Round, render_centered_prompt, result_color, step_selection,
+};
+use crate::utils::center;
+use crossterm::event::{KeyCode, KeyEvent};
+use rand::Rng;
+use rand::prelude::{IndexedRandom, SliceRandom};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::prelude::Alignment::Center;
+use ratatui::prelude::{Color, Line, Style, Stylize, Widget};
+use ratatui::text::Span;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+
+/// Status flags a CPU sets after an addition or subtraction
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Flags {
+    /// Unsigned result didn't fit: carry out of an addition, borrow of a subtraction (like x86)
+    pub carry: bool,
+    /// Signed two's complement result didn't fit
+    pub overflow: bool,
+    pub zero: bool,
+    /// Sign bit of the result
+    pub negative: bool,
+}
+
+impl Flags {
+    /// Hotkey, name and value of each flag, in the order CPUs usually list them
+    fn entries(self) -> [(char, &'static str, bool); 4] {
+        [
+            ('C', "carry", self.carry),
+            ('V', "overflow", self.overflow),
+            ('Z', "zero", self.zero),
+            ('N', "negative", self.negative),
+        ]
+    }
+
+    const fn toggle(&mut self, flag: char) {
+        match flag {
+            'C' => self.carry = !self.carry,
+            'V' => self.overflow = !self.overflow,
+            'Z' => self.zero = !self.zero,
+            'N' => self.negative = !self.negative,
+            _ => {},
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ArithOp {
+    Add,
+    Sub,
+}
+
+impl ArithOp {
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+        }
+    }
+
+    /// Wrapped `num_bits` wide result and the flags it sets
+    pub fn apply(self, left: u64, right: u64, num_bits: u32) -> (u64, Flags) {
+        let unsigned = |raw| NumberMode::Unsigned.value_of(raw, num_bits);
+        let signed = |raw| NumberMode::Signed.value_of(raw, num_bits);
+        let (unsigned_result, signed_result) = match self {
+            Self::Add => (unsigned(left) + unsigned(right), signed(left) + signed(right)),
+            Self::Sub => (unsigned(left) - unsigned(right), signed(left) - signed(right)),
+        };
+        let modulus = 1i64 << num_bits;
+        #[allow(clippy::cast_sign_loss)]
+        let result = unsigned_result.rem_euclid(modulus) as u64;
+        let flags = Flags {
+            carry: !(0..modulus).contains(&unsigned_result),
+            overflow: !(-(modulus / 2)..modulus / 2).contains(&signed_result),
+            zero: result == 0,
+            negative: result >> (num_bits - 1) & 1 == 1,
+        };
+        (result, flags)
+    }
+}
+
+/// First the wrapped result is picked, then the flags it sets are asked as a follow-up
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Stage {
+    Result,
+    Flags,
+}
+
+/// Shows `a + b` or `a - b` in binary. A correct result leads to a follow-up question
+/// about the carry, overflow, zero and negative flags, and the round counts only if both are right.
+pub struct ArithmeticPuzzle {
+    num_bits: u32,
+    op: ArithOp,
+    left: u64,
+    right: u64,
+    answer: u64,
+    flags: Flags,
+    suggestions: Vec<u64>,
+    selected_suggestion: Option<u64>,
+    stage: Stage,
+    guessed_flags: Flags,
+    round: Round,
+}
+
+impl Puzzle for ArithmeticPuzzle {
+    fn new(config: &PuzzleConfig, streak: u32, rng: &mut impl Rng) -> Self {
+        let num_bits = config.bits.to_int();
+        let mask = config.bits.mask();
+
+        let op = *[ArithOp::Add, ArithOp::Sub].choose(rng).unwrap_or(&ArithOp::Add);
+        let left = rng.random_range(0..=mask);
+        let right = rng.random_range(0..=mask);
+        let (answer, flags) = op.apply(left, right, num_bits);
+
+        // Typical slips: the other operation, swapped operands, a lost carry and off by one
+        let other_op = match op {
+            ArithOp::Add => ArithOp::Sub,
+            ArithOp::Sub => ArithOp::Add,
+        };
+        let mut near_misses = vec![
+            other_op.apply(left, right, num_bits).0,
+            op.apply(right, left, num_bits).0,
+            answer ^ 1 << (num_bits - 1),
+            answer.wrapping_add(1) & mask,
+            answer.wrapping_sub(1) & mask,
+        ];
+        near_misses.shuffle(rng);
+
+        let mut suggestions = vec![answer];
+        for candidate in near_misses {
+            if suggestions.len() < config.bits.suggestion_count()
+                && !suggestions.contains(&candidate)
+            {
+                suggestions.push(candidate);
+            }
+        }
+        while suggestions.len() < config.bits.suggestion_count() {
+            let candidate = rng.random_range(0..=mask);
+            if !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+            }
+        }
+        suggestions.shuffle(rng);
+
+        Self {
+            num_bits,
+            op,
+            left,
+            right,
+            answer,
+            flags,
+            selected_suggestion: Some(suggestions[0]),
+            suggestions,
+            stage: Stage::Result,
+            guessed_flags: Flags::default(),
+            round: Round::new(streak),
+        }
+    }
+
+    fn round(&self) -> &Round {
+        &self.round
+    }
+
+    fn round_mut(&mut self) -> &mut Round {
+        &mut self.round
+    }
+
+    fn handle_answer_input(&mut self, input: KeyEvent) {
+        match self.stage {
+            Stage::Result => self.handle_result_input(input),
+            Stage::Flags => self.handle_flags_input(input),
+        }
+    }
+
+    fn answer_height(&self, width: u16) -> u16 {
+        match self.stage {
+            Stage::Result => self.choices().height(width),
+            Stage::Flags => 3,
+        }
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let operator = Span::styled(self.op.symbol(), Style::default().fg(Color::LightCyan));
+        let mut lines = vec![
+            Line::from(format!("    {}", self.binary(self.left))),
+            Line::from(vec![operator, Span::raw(format!("   {}", self.binary(self.right)))]),
+        ];
+        if self.stage == Stage::Flags {
+            lines.push(Line::from(format!("=   {}", self.binary(self.answer))));
+        }
+        render_centered_prompt(lines, area, buf);
+    }
+
+    fn render_answer(&self, area: Rect, buf: &mut Buffer) {
+        match self.stage {
+            Stage::Result => self.choices().render_ref(area, buf),
+            Stage::Flags => self.render_flags(area, buf),
+        }
+    }
+
+    fn answer_hint(&self, width: u16) -> (&'static str, &'static str) {
+        match self.stage {
+            Stage::Result => self.choices().hint(width),
+            Stage::Flags => ("C V Z N", "toggle  "),
+        }
+    }
+}
+
+impl ArithmeticPuzzle {
+    fn binary(&self, raw_bits: u64) -> String {
+        grouped_binary(raw_bits, self.num_bits, 4)
+    }
+
+    fn handle_result_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_right(x) || keybinds::is_down(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, true);
+            },
+            x if keybinds::is_left(x) || keybinds::is_up(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, false);
+            },
+            x if keybinds::is_select(x) => match self.selected_suggestion {
+                Some(selected) if selected == self.answer => {
+                    // the follow-up question gets a fresh countdown
+                    self.stage = Stage::Flags;
+                    self.round.time_left = self.round.time_total;
+                },
+                Some(_) => self.round.resolve(false),
+                None => {},
+            },
+            _ => {},
+        }
+    }
+
+    fn handle_flags_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_select(x) => self.round.resolve(self.guessed_flags == self.flags),
+            KeyEvent { code: KeyCode::Char(c), .. } => {
+                self.guessed_flags.toggle(c.to_ascii_uppercase());
+            },
+            _ => {},
+        }
+    }
+
+    fn render_flags(&self, area: Rect, buf: &mut Buffer) {
+        Block::bordered()
+            .border_type(BorderType::Double)
+            .fg(result_color(self.round.guess_result))
+            .render(area, buf);
+
+        let mut spans = Vec::new();
+        for ((key, name, guessed), (_, _, actual)) in
+            self.guessed_flags.entries().into_iter().zip(self.flags.entries())
+        {
+            // once resolved, wrong flags turn red, so the correct value is just the other one
+            let value_color = match self.round.guess_result {
+                Some(GuessResult::Correct) => Color::Green,
+                Some(_) if guessed != actual => Color::Red,
+                _ => Color::White,
+            };
+            if !spans.is_empty() {
+                spans.push(Span::raw("   "));
+            }
+            spans.push(Span::styled(key.to_string(), Style::default().fg(Color::LightCyan)));
+            spans.push(Span::styled(format!(" {name} "), Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                u8::from(guessed).to_string(),
+                Style::default().fg(value_color).bold(),
+            ));
+        }
+
+        let line = Line::from(spans);
+        #[allow(clippy::cast_possible_truncation)]
+        let width = line.width() as u16;
+        Paragraph::new(line).alignment(Center).render(center(area, Constraint::Length(width)), buf);
+    }
+
+    fn choices(&self) -> Choices {
+        Choices {
+            labels: self.suggestions.iter().map(|&s| self.binary(s)).collect(),
+            selected: self
+                .selected_suggestion
+                .and_then(|selected| self.suggestions.iter().position(|&s| s == selected)),
+            correct: self.suggestions.iter().position(|&s| s == self.answer).unwrap_or(0),
+            result: self.round.guess_result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_numbers::{Bits, PuzzleKind};
+    use crossterm::event::{KeyEventKind, KeyEventState, KeyModifiers};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[test]
+    fn flags_follow_unsigned_carry_and_signed_overflow() {
+        // 127 + 1: fits unsigned, overflows signed
+        let (result, flags) = ArithOp::Add.apply(0x7F, 0x01, 8);
+        assert_eq!(result, 0x80);
+        assert_eq!(flags, Flags { carry: false, overflow: true, zero: false, negative: true });
+
+        // 255 + 1 wraps to zero: carry out, but -1 + 1 is fine signed
+        let (result, flags) = ArithOp::Add.apply(0xFF, 0x01, 8);
+        assert_eq!(result, 0);
+        assert_eq!(flags, Flags { carry: true, overflow: false, zero: true, negative: false });
+
+        // 0 - 1 borrows, -8 - 1 overflows in 4 bit
+        let (result, flags) = ArithOp::Sub.apply(0b0000, 0b0001, 4);
+        assert_eq!(result, 0b1111);
+        assert!(flags.carry && !flags.overflow && flags.negative);
+        let (result, flags) = ArithOp::Sub.apply(0b1000, 0b0001, 4);
+        assert_eq!(result, 0b0111);
+        assert!(!flags.carry && flags.overflow && !flags.negative);
+    }
+
+    #[test]
+    fn correct_result_leads_to_flags_question() {
+        let config =
+            PuzzleConfig::new(Bits::Eight, NumberMode::Signed).with_kind(PuzzleKind::Arithmetic);
+        assert_eq!(config.high_score_key(), "8s-arith");
+        let mut p = ArithmeticPuzzle::new(&config, 0, &mut ChaCha8Rng::seed_from_u64(7));
+        p.selected_suggestion = Some(p.answer);
+        p.round.time_left = 1.0;
+        p.handle_answer_input(key(KeyCode::Enter));
+        assert_eq!(p.stage, Stage::Flags);
+        assert_eq!(p.round.guess_result, None);
+        assert!((p.round.time_left - p.round.time_total).abs() < f64::EPSILON);
+
+        for (flag, _, set) in p.flags.entries() {
+            if set {
+                p.handle_answer_input(key(KeyCode::Char(flag.to_ascii_lowercase())));
+            }
+        }
+        p.handle_answer_input(key(KeyCode::Enter));
+        assert_eq!(p.round.guess_result, Some(GuessResult::Correct));
+    }
+
+    #[test]
+    fn wrong_result_ends_the_round_without_flags_question() {
+        let config =
+            PuzzleConfig::new(Bits::Four, NumberMode::Unsigned).with_kind(PuzzleKind::Arithmetic);
+        let mut p = ArithmeticPuzzle::new(&config, 0, &mut ChaCha8Rng::seed_from_u64(7));
+        p.selected_suggestion = p.suggestions.iter().copied().find(|&s| s != p.answer);
+        p.handle_answer_input(key(KeyCode::Enter));
+        assert_eq!(p.stage, Stage::Result);
+        assert_eq!(p.round.guess_result, Some(GuessResult::Incorrect));
+    }
+}
@@ -0,0 +1,304 @@
+use crate::binary_numbers::{PuzzleConfig, grouped_binary};
+use crate::keybinds;
+use crate::main_screen_widget::WidgetRef;
+use crate::puzzle::{Choices, Puzzle, Round, render_centered_prompt, step_selection};
+use crossterm::event::KeyEvent;
+use rand::Rng;
+use rand::prelude::SliceRandom;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Style};
+use ratatui::text::Span;
+
+/// The three questions a BCD round can ask, picked at random each round
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum BcdQuestion {
+    /// Packed BCD pattern to decimal
+    Decode,
+    /// Decimal to packed BCD pattern
+    Encode,
+    /// Which of the patterns is valid BCD
+    Validity,
+}
+
+/// Packed BCD of a decimal number, one nibble per digit.
+/// Only the lowest 16 digits fit into the 64 bits, the others are dropped.
+pub fn bcd_encode(mut value: u64) -> u64 {
+    let mut bcd = 0;
+    let mut shift = 0;
+    while value > 0 && shift < u64::BITS {
+        bcd |= (value % 10) << shift;
+        value /= 10;
+        shift += 4;
+    }
+    bcd
+}
+
+/// Decimal number of a packed BCD pattern, reading invalid nibbles as if they were digits
+pub fn bcd_decode(mut bcd: u64) -> u64 {
+    let mut value = 0;
+    let mut scale = 1;
+    while bcd > 0 {
+        value += (bcd & 0xF) * scale;
+        bcd >>= 4;
+        scale *= 10;
+    }
+    value
+}
+
+/// Every nibble is a digit 0 to 9, 1010 to 1111 are not
+pub const fn is_valid_bcd(mut bcd: u64) -> bool {
+    while bcd > 0 {
+        if bcd & 0xF > 9 {
+            return false;
+        }
+        bcd >>= 4;
+    }
+    true
+}
+
+/// One nibble per decimal digit, so an 8 bit byte holds two digits (0 to 99).
+/// Suggestions are numbers when decoding and bit patterns otherwise.
+pub struct BcdPuzzle {
+    num_bits: u32,
+    question: BcdQuestion,
+    /// Always valid BCD, shown as the number it stands for when asking for the encoding
+    pattern: u64,
+    suggestions: Vec<u64>,
+    selected_suggestion: Option<u64>,
+    round: Round,
+}
+
+impl Puzzle for BcdPuzzle {
+    fn new(config: &PuzzleConfig, streak: u32, rng: &mut impl Rng) -> Self {
+        let num_bits = config.bits.to_int();
+        let digits = num_bits / 4;
+        let largest = 10u64.pow(digits) - 1;
+        let question = match rng.random_range(0..3) {
+            0 => BcdQuestion::Decode,
+            1 => BcdQuestion::Encode,
+            _ => BcdQuestion::Validity,
+        };
+
+        let pattern = bcd_encode(rng.random_range(0..=largest));
+        let number = bcd_decode(pattern);
+        let answer = match question {
+            BcdQuestion::Decode => number,
+            BcdQuestion::Encode | BcdQuestion::Validity => pattern,
+        };
+
+        // Typical slips: digits misread or swapped, the pattern read as plain binary
+        let mut near_misses = match question {
+            BcdQuestion::Decode => vec![
+                pattern,
+                swap_lowest_digits(number),
+                number.wrapping_add(1),
+                number.wrapping_sub(1),
+                number.wrapping_add(10),
+            ],
+            BcdQuestion::Encode => [
+                Some(number),
+                Some(bcd_encode(swap_lowest_digits(number))),
+                Some(bcd_encode(number + 1)),
+                number.checked_sub(1).map(bcd_encode),
+                // the lowest digit written plus 6, as if a decimal adjust was missed
+                Some(pattern + 6),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            BcdQuestion::Validity => (0..4).map(|_| random_invalid(rng, digits)).collect(),
+        };
+        near_misses.shuffle(rng);
+
+        let in_range = |candidate: u64| match question {
+            BcdQuestion::Decode => candidate <= largest,
+            BcdQuestion::Encode => candidate < 1 << num_bits,
+            BcdQuestion::Validity => !is_valid_bcd(candidate),
+        };
+        let mut suggestions = vec![answer];
+        for candidate in near_misses {
+            if suggestions.len() < config.bits.suggestion_count()
+                && !suggestions.contains(&candidate)
+                && in_range(candidate)
+            {
+                suggestions.push(candidate);
+            }
+        }
+        while suggestions.len() < config.bits.suggestion_count() {
+            let candidate = match question {
+                BcdQuestion::Decode => rng.random_range(0..=largest),
+                BcdQuestion::Encode => bcd_encode(rng.random_range(0..=largest)),
+                BcdQuestion::Validity => random_invalid(rng, digits),
+            };
+            if !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+            }
+        }
+        suggestions.shuffle(rng);
+
+        Self {
+            num_bits,
+            question,
+            pattern,
+            selected_suggestion: Some(suggestions[0]),
+            suggestions,
+            round: Round::new(streak),
+        }
+    }
+
+    fn round(&self) -> &Round {
+        &self.round
+    }
+
+    fn round_mut(&mut self) -> &mut Round {
+        &mut self.round
+    }
+
+    fn handle_answer_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_right(x) || keybinds::is_down(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, true);
+            },
+            x if keybinds::is_left(x) || keybinds::is_up(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, false);
+            },
+            x if keybinds::is_select(x) => {
+                if let Some(selected) = self.selected_suggestion {
+                    self.round.resolve(selected == self.answer());
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn answer_height(&self, width: u16) -> u16 {
+        self.choices().height(width)
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let (prompt, note) = match self.question {
+            BcdQuestion::Decode => (self.binary(self.pattern), "BCD → decimal"),
+            BcdQuestion::Encode => (bcd_decode(self.pattern).to_string(), "decimal → BCD"),
+            BcdQuestion::Validity => {
+                ("which one is valid BCD?".to_string(), "no nibble above 1001")
+            },
+        };
+        let lines = vec![
+            Line::from(prompt),
+            Line::from(Span::styled(note, Style::default().fg(Color::DarkGray))),
+        ];
+        render_centered_prompt(lines, area, buf);
+    }
+
+    fn render_answer(&self, area: Rect, buf: &mut Buffer) {
+        self.choices().render_ref(area, buf);
+    }
+
+    fn answer_hint(&self, width: u16) -> (&'static str, &'static str) {
+        self.choices().hint(width)
+    }
+}
+
+impl BcdPuzzle {
+    fn answer(&self) -> u64 {
+        match self.question {
+            BcdQuestion::Decode => bcd_decode(self.pattern),
+            BcdQuestion::Encode | BcdQuestion::Validity => self.pattern,
+        }
+    }
+
+    fn binary(&self, raw_bits: u64) -> String {
+        grouped_binary(raw_bits, self.num_bits, 4)
+    }
+
+    fn choices(&self) -> Choices {
+        Choices {
+            labels: self
+                .suggestions
+                .iter()
+                .map(|&s| match self.question {
+                    BcdQuestion::Decode => s.to_string(),
+                    BcdQuestion::Encode | BcdQuestion::Validity => self.binary(s),
+                })
+                .collect(),
+            selected: self
+                .selected_suggestion
+                .and_then(|selected| self.suggestions.iter().position(|&s| s == selected)),
+            correct: self.suggestions.iter().position(|&s| s == self.answer()).unwrap_or(0),
+            result: self.round.guess_result,
+        }
+    }
+}
+
+/// Valid BCD of `digits` digits, except for one nibble above 1001
+fn random_invalid(rng: &mut impl Rng, digits: u32) -> u64 {
+    let nibble = rng.random_range(0..digits) * 4;
+    let valid = bcd_encode(rng.random_range(0..10u64.pow(digits)));
+    (valid & !(0xF << nibble)) | (rng.random_range(10..16) << nibble)
+}
+
+/// The number with its two lowest digits swapped, e.g. 47 becomes 74
+fn swap_lowest_digits(value: u64) -> u64 {
+    let ones = value % 10;
+    let tens = value / 10 % 10;
+    value - ones - tens * 10 + ones * 10 + tens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::NumberMode;
+    use crate::binary_numbers::{Bits, PuzzleKind};
+    use crate::puzzle::{assert_unique, seeded_puzzles};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn packs_one_digit_per_nibble() {
+        assert_eq!(bcd_encode(49), 0b0100_1001);
+        assert_eq!(bcd_encode(9999), 0x9999);
+        assert_eq!(bcd_decode(0x0250), 250);
+        assert!(is_valid_bcd(0x0987));
+        assert!(!is_valid_bcd(0x09A7));
+        assert_eq!(swap_lowest_digits(147), 174);
+        // the 20 digits of the largest number don't fit, the lowest 16 do
+        assert_eq!(bcd_encode(u64::MAX), 0x6744_0737_0955_1615);
+    }
+
+    #[test]
+    fn encoding_zero_has_no_near_miss_below_it() {
+        let config = PuzzleConfig::new(Bits::Four, NumberMode::Unsigned).with_kind(PuzzleKind::Bcd);
+        let zero = (0..500)
+            .map(|seed| BcdPuzzle::new(&config, 0, &mut ChaCha8Rng::seed_from_u64(seed)))
+            .find(|p| p.question == BcdQuestion::Encode && p.pattern == 0);
+        let Some(p) = zero else {
+            panic!("no seed asks to encode 0");
+        };
+        assert_eq!(p.suggestions.len(), Bits::Four.suggestion_count());
+        assert!(p.suggestions.contains(&0));
+        assert_unique(&p.suggestions);
+    }
+
+    #[test]
+    fn puzzle_offers_unique_suggestions_with_one_valid_answer() {
+        let config =
+            PuzzleConfig::new(Bits::Twelve, NumberMode::Unsigned).with_kind(PuzzleKind::Bcd);
+        assert_eq!(config.high_score_key(), "12-bcd");
+        for p in seeded_puzzles::<BcdPuzzle>(&config) {
+            assert!(is_valid_bcd(p.pattern) && p.pattern < 1 << 12);
+            assert_eq!(p.suggestions.len(), Bits::Twelve.suggestion_count());
+            assert!(p.suggestions.contains(&p.answer()));
+            assert_unique(&p.suggestions);
+            match p.question {
+                BcdQuestion::Decode => assert!(p.suggestions.iter().all(|&s| s <= 999)),
+                BcdQuestion::Encode => assert!(p.suggestions.iter().all(|&s| s < 1 << 12)),
+                BcdQuestion::Validity => {
+                    assert_eq!(p.suggestions.iter().filter(|&&s| is_valid_bcd(s)).count(), 1);
+                },
+            }
+        }
+    }
+}
@@ -1,10 +1,22 @@
 use crate::app::{NumberMode, get_mode_color};
+use crate::arithmetic::ArithmeticPuzzle;
+use crate::bcd::BcdPuzzle;
+use crate::bitwise::BitwisePuzzle;
+use crate::daily;
+use crate::fixed_point::{FixedPointPuzzle, q_format};
+use crate::flashcards;
+use crate::float::HalfFloatPuzzle;
 use crate::keybinds;
 use crate::main_screen_widget::{MainScreenWidget, WidgetRef};
-use crate::utils::{When, center};
+use crate::mixed;
+use crate::puzzle::{Choices, GuessResult, Puzzle, Round, result_color, step_selection};
+use crate::storage;
+use crate::utils::center;
+use crate::weakness::{Attempt, WeakSpots, Weaknesses};
 use crossterm::event::{KeyCode, KeyEvent};
-use rand::Rng;
 use rand::prelude::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
 use ratatui::prelude::Alignment::Center;
@@ -13,60 +25,35 @@ use ratatui::style::Modifier;
 use ratatui::text::Span;
 use ratatui::widgets::BorderType::Double;
 use ratatui::widgets::{Block, BorderType, Paragraph};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write as _;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::Read;
 
-struct StatsSnapshot {
-    score: u32,
-    streak: u32,
-    max_streak: u32,
-    rounds: u32,
-    lives: u32,
-    bits: Bits,
-    number_mode: NumberMode,
-    hearts: String,
-    game_state: GameState,
-    prev_high_score: u32,
-    new_high_score: bool,
-}
-
-impl WidgetRef for BinaryNumbersGame {
+impl<P: Puzzle> WidgetRef for BinaryNumbersGame<P> {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
         let [game_column] = Layout::horizontal([Constraint::Length(65)])
             .flex(Flex::Center)
             .horizontal_margin(1)
             .areas(area);
 
-        self.puzzle.render_ref(game_column, buf);
-    }
-}
-
-impl WidgetRef for BinaryNumbersPuzzle {
-    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
-        let [middle] =
-            Layout::horizontal([Constraint::Percentage(100)]).flex(Flex::Center).areas(area);
-
+        let answer_height = self.puzzle.answer_height(game_column.width);
         let [stats_area, current_number_area, suggestions_area, progress_bar_area, result_area] =
             Layout::vertical([
                 Constraint::Length(4),
                 Constraint::Length(5),
-                Constraint::Length(3),
+                Constraint::Length(answer_height),
                 Constraint::Length(4),
                 Constraint::Length(5),
             ])
             .flex(Flex::Center)
             .horizontal_margin(0)
-            .areas(middle);
+            .areas(game_column);
 
         self.render_stats_area(stats_area, buf);
 
-        if let Some(stats) = &self.stats_snapshot
-            && stats.game_state == GameState::GameOver
-        {
-            render_game_over(
-                stats,
+        if self.game_state == GameState::GameOver {
+            self.render_game_over(
                 current_number_area,
                 suggestions_area,
                 progress_bar_area,
@@ -76,130 +63,82 @@ impl WidgetRef for BinaryNumbersPuzzle {
             return;
         }
 
-        self.render_current_number(current_number_area, buf);
-        self.render_suggestions(suggestions_area, buf);
+        self.puzzle.render_prompt(current_number_area, buf);
+        self.puzzle.render_answer(suggestions_area, buf);
         self.render_status_and_timer(progress_bar_area, buf);
         self.render_instructions(result_area, buf);
     }
 }
 
-impl BinaryNumbersPuzzle {
+impl<P: Puzzle> BinaryNumbersGame<P> {
     fn render_stats_area(&self, area: Rect, buf: &mut Buffer) {
         Block::bordered().title_alignment(Center).dark_gray().render(area, buf);
 
-        if let Some(stats) = &self.stats_snapshot {
-            let high_label = if stats.new_high_score {
-                let style = Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD);
-                Span::styled(format!("Hi-Score: {}*  ", stats.score), style)
-            } else {
-                let style = Style::default().fg(Color::DarkGray);
-                Span::styled(format!("Hi-Score: {}  ", stats.prev_high_score), style)
-            };
+        let high_label = if self.new_high_score_reached {
+            let style = Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD);
+            Span::styled(format!("Hi-Score: {}*  ", self.score), style)
+        } else {
+            let style = Style::default().fg(Color::DarkGray);
+            Span::styled(format!("Hi-Score: {}  ", self.prev_high_score_for_display), style)
+        };
 
-            let mode_color = get_mode_color(&stats.bits);
-            let mode_label = format!("{} {}", stats.bits.label(), stats.number_mode.label());
-            let line1 = Line::from(vec![
-                Span::styled(format!("Mode: {}  ", mode_label), Style::default().fg(mode_color)),
-                high_label,
-            ]);
+        let mode_color = get_mode_color(&self.config.bits);
+        let mut line1 = Line::from(vec![Span::styled(
+            format!("Mode: {}  ", self.config.mode_label()),
+            Style::default().fg(mode_color),
+        )]);
 
-            let line2 = Line::from(vec![
+        let rounds = match self.round_limit() {
+            Some(limit) => format!("{}/{limit}", self.rounds),
+            None => self.rounds.to_string(),
+        };
+        let mut line2 = if !self.config.game_mode.keeps_score() {
+            // practice has no score, only how well the session goes
+            let (correct, answered) = self.accuracy();
+            let percent = (correct * 100).checked_div(answered).unwrap_or(0);
+            Line::from(vec![
                 Span::styled(
-                    format!("Score: {}  ", stats.score),
+                    format!("Correct: {correct}/{answered}  "),
                     Style::default().fg(Color::Green),
                 ),
                 Span::styled(
-                    format!("Streak: {}  ", stats.streak),
-                    Style::default().fg(Color::Cyan),
-                ),
-                Span::styled(
-                    format!("Max: {}  ", stats.max_streak),
-                    Style::default().fg(Color::Blue),
-                ),
-                Span::styled(
-                    format!("Rounds: {}  ", stats.rounds),
+                    format!("Accuracy: {percent}%  "),
                     Style::default().fg(Color::Magenta),
                 ),
-                Span::styled(format!("Lives: {}  ", stats.hearts), Style::default().fg(Color::Red)),
-            ]);
-
-            #[allow(clippy::cast_possible_truncation)]
-            let widest = line1.width().max(line2.width()) as u16;
-            Paragraph::new(vec![line1, line2])
-                .alignment(Center)
-                .render(center(area, Constraint::Length(widest)), buf);
-        }
-    }
-
-    fn render_current_number(&self, area: Rect, buf: &mut Buffer) {
-        let [inner] =
-            Layout::horizontal([Constraint::Percentage(100)]).flex(Flex::Center).areas(area);
-
-        Block::bordered()
-            .border_type(Double)
-            .border_style(Style::default().dark_gray())
-            .render(inner, buf);
-
-        let binary_string = self.current_to_binary_string();
-        let scale_suffix = match self.bits {
-            Bits::FourShift4 => Some(" x16"),
-            Bits::FourShift8 => Some(" x256"),
-            Bits::FourShift12 => Some(" x4096"),
-            _ => None,
+            ])
+        } else {
+            line1.push_span(high_label);
+            Line::from(vec![Span::styled(
+                format!("Score: {}  ", self.score),
+                Style::default().fg(Color::Green),
+            )])
         };
-        let mut spans = vec![Span::raw(binary_string)];
-        if let Some(sfx) = scale_suffix {
-            spans.push(Span::styled(sfx, Style::default().fg(Color::DarkGray)));
+        line2.push_span(Span::styled(
+            format!("Streak: {}  ", self.streak),
+            Style::default().fg(Color::Cyan),
+        ));
+        line2.push_span(Span::styled(
+            format!("Max: {}  ", self.max_streak),
+            Style::default().fg(Color::Blue),
+        ));
+        if self.config.game_mode != GameMode::Zen {
+            line2.push_span(Span::styled(
+                format!("Rounds: {rounds}  "),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        if self.config.game_mode.has_lives() {
+            line2.push_span(Span::styled(
+                format!("Lives: {}  ", self.lives_hearts()),
+                Style::default().fg(Color::Red),
+            ));
         }
+
         #[allow(clippy::cast_possible_truncation)]
-        let total_width = spans.iter().map(ratatui::prelude::Span::width).sum::<usize>() as u16;
-        let lines: Vec<Line> = vec![Line::from(spans)];
-        Paragraph::new(lines)
+        let widest = line1.width().max(line2.width()) as u16;
+        Paragraph::new(vec![line1, line2])
             .alignment(Center)
-            .render(center(inner, Constraint::Length(total_width)), buf);
-    }
-
-    fn render_suggestions(&self, area: Rect, buf: &mut Buffer) {
-        let suggestions = self.suggestions();
-        let suggestions_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(vec![Constraint::Min(6); suggestions.len()])
-            .split(area);
-
-        for (i, suggestion) in suggestions.iter().enumerate() {
-            let item_is_selected = self.selected_suggestion == Some(*suggestion);
-            let show_correct_number = self.guess_result.is_some();
-            let is_correct_number = self.is_correct_guess(*suggestion);
-            let area = suggestions_layout[i];
-
-            let border_type = if item_is_selected {
-                BorderType::Double
-            } else {
-                BorderType::Plain
-            };
-
-            let border_color = if item_is_selected {
-                match self.guess_result {
-                    Some(GuessResult::Correct) => Color::Green,
-                    Some(GuessResult::Incorrect) => Color::Red,
-                    Some(GuessResult::Timeout) => Color::Yellow,
-                    None => Color::LightCyan,
-                }
-            } else {
-                Color::DarkGray
-            };
-
-            Block::bordered().border_type(border_type).fg(border_color).render(area, buf);
-
-            let suggestion_str = format!("{suggestion}");
-
-            #[allow(clippy::cast_possible_truncation)]
-            Paragraph::new(suggestion_str.to_string())
-                .white()
-                .when(show_correct_number && is_correct_number, |p| p.light_green().underlined())
-                .alignment(Center)
-                .render(center(area, Constraint::Length(suggestion_str.len() as u16)), buf);
-        }
+            .render(center(area, Constraint::Length(widest)), buf);
     }
 
     fn render_status_and_timer(&self, area: Rect, buf: &mut Buffer) {
@@ -209,7 +148,11 @@ impl BinaryNumbersPuzzle {
             .areas(area);
 
         self.render_status(left, buf);
-        self.render_timer(right, buf);
+        if self.config.game_mode == GameMode::Zen {
+            self.render_hints(right, buf);
+        } else {
+            self.render_timer(right, buf);
+        }
     }
 
     fn render_status(&self, area: Rect, buf: &mut Buffer) {
@@ -220,7 +163,12 @@ impl BinaryNumbersPuzzle {
             .title_style(Style::default().white())
             .render(area, buf);
 
-        if let Some(result) = &self.guess_result {
+        let round = self.puzzle.round();
+        let outcome = round
+            .guess_result
+            .map(|result| (result, round.last_points_awarded))
+            .or(self.previous_outcome);
+        if let Some((result, points)) = outcome {
             let (icon, line1_text, color) = match result {
                 GuessResult::Correct => (":)", "success", Color::Green),
                 GuessResult::Incorrect => (":(", "incorrect", Color::Red),
@@ -228,8 +176,20 @@ impl BinaryNumbersPuzzle {
             };
 
             let gained_line = match result {
-                GuessResult::Correct => format!("gained {} points", self.last_points_awarded),
-                GuessResult::Incorrect => "lost a life".to_string(),
+                // a card is rescheduled whatever the answer
+                _ if let Some(study) = &self.study => study.verdict(),
+                GuessResult::Correct if self.config.game_mode == GameMode::Zen => {
+                    "keep it up".to_string()
+                },
+                GuessResult::Correct => format!("gained {points} points"),
+                GuessResult::Incorrect => self.config.game_mode.wrong_answer_cost(),
+                // without a timer the only way to run out of time is to skip
+                GuessResult::Timeout if self.config.game_mode == GameMode::Zen => {
+                    "skipped".to_string()
+                },
+                GuessResult::Timeout if self.countdown.is_some() => {
+                    self.config.game_mode.wrong_answer_cost()
+                },
                 GuessResult::Timeout => "timeout".to_string(),
             };
 
@@ -247,7 +207,8 @@ impl BinaryNumbersPuzzle {
     }
 
     fn render_timer(&self, area: Rect, buf: &mut Buffer) {
-        let ratio = self.time_left / self.time_total;
+        let round = self.countdown.as_ref().unwrap_or_else(|| self.puzzle.round());
+        let ratio = round.time_left / round.time_total;
         let gauge_color = if ratio > 0.6 {
             Color::Green
         } else if ratio > 0.3 {
@@ -270,106 +231,367 @@ impl BinaryNumbersPuzzle {
         render_ascii_gauge(gauge_line, buf, ratio, gauge_color);
 
         Paragraph::new(Line::from(Span::styled(
-            format!("{:.2} seconds left", self.time_left),
+            format!("{:.2} seconds left", round.time_left),
             Style::default().fg(gauge_color),
         )))
         .alignment(Center)
         .render(time_line, buf);
     }
 
+    /// The hints revealed so far, in place of the timer of practice games
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .dark_gray()
+            .title("Hints")
+            .title_style(Style::default().white())
+            .title_alignment(Center);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let hints = self.puzzle.hints();
+        let shown = self.puzzle.round().hints_shown.min(hints.len());
+        // the instructions are full already, so the panel tells how to get a hint
+        let lines: Vec<Line> = if hints.is_empty() {
+            vec![Line::from("no hints for this puzzle".dark_gray())]
+        } else if shown == 0 {
+            vec![Line::from("press ? for a hint".dark_gray())]
+        } else {
+            hints[..shown].iter().map(|hint| Line::from(hint.clone().light_cyan())).collect()
+        };
+        Paragraph::new(lines).alignment(Center).render(inner, buf);
+    }
+
     fn render_instructions(&self, area: Rect, buf: &mut Buffer) {
         Block::bordered().dark_gray().render(area, buf);
 
+        let (answer_keys, answer_description) = self.puzzle.answer_hint(area.width);
         let instruction_spans: Vec<Span> = [
-            hotkey_span("Left Right", "select  "),
+            hotkey_span(answer_keys, answer_description),
             hotkey_span("Enter", "confirm  "),
             hotkey_span("S", "skip  "),
             hotkey_span("Esc", "exit"),
         ]
-        .iter()
+        .into_iter()
         .flatten()
-        .cloned()
         .collect();
 
         Paragraph::new(vec![Line::from(instruction_spans)])
             .alignment(Center)
             .render(center(area, Constraint::Length(65)), buf);
     }
-}
 
-fn hotkey_span<'a>(key: &'a str, description: &str) -> Vec<Span<'a>> {
-    vec![
-        Span::styled("<", Style::default().fg(Color::White)),
-        Span::styled(key, Style::default().fg(Color::LightCyan)),
-        Span::styled(format!("> {description}"), Style::default().fg(Color::White)),
-    ]
-}
+    fn render_game_over(
+        &self,
+        current_number_area: Rect,
+        suggestions_area: Rect,
+        progress_bar_area: Rect,
+        result_area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let combined_rect = Rect {
+            x: current_number_area.x,
+            y: current_number_area.y,
+            width: current_number_area.width,
+            height: current_number_area.height
+                + suggestions_area.height
+                + progress_bar_area.height
+                + result_area.height,
+        };
+        Block::bordered()
+            .border_style(Style::default().fg(Color::DarkGray))
+            .render(combined_rect, buf);
 
-fn render_game_over(
-    stats: &StatsSnapshot,
-    current_number_area: Rect,
-    suggestions_area: Rect,
-    progress_bar_area: Rect,
-    result_area: Rect,
-    buf: &mut Buffer,
-) {
-    let combined_rect = Rect {
-        x: current_number_area.x,
-        y: current_number_area.y,
-        width: current_number_area.width,
-        height: current_number_area.height
-            + suggestions_area.height
-            + progress_bar_area.height
-            + result_area.height,
-    };
-    Block::bordered().border_style(Style::default().fg(Color::DarkGray)).render(combined_rect, buf);
-
-    let mut lines = vec![
-        Line::from(Span::styled(
-            format!("Final Score: {}", stats.score),
+        let mut lines = vec![Line::from(Span::styled(
+            format!("Final Score: {}", self.score),
             Style::default().fg(Color::Green),
-        )),
-        Line::from(Span::styled(
-            format!("Previous High: {}", stats.prev_high_score),
-            Style::default().fg(Color::Yellow),
-        )),
-        Line::from(Span::styled(
-            format!("Rounds Played: {}", stats.rounds),
-            Style::default().fg(Color::Magenta),
-        )),
-        Line::from(Span::styled(
-            format!("Max Streak: {}", stats.max_streak),
-            Style::default().fg(Color::Cyan),
-        )),
-    ];
-    if stats.new_high_score {
-        lines.insert(
-            1,
+        ))];
+        // the score of the day is told with the rest of the daily result, study has none
+        if !matches!(self.config.game_mode, GameMode::Daily { .. } | GameMode::Study) {
+            lines.push(Line::from(Span::styled(
+                format!("Previous High: {}", self.prev_high_score_for_display),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        lines.extend([
             Line::from(Span::styled(
-                "NEW HIGH SCORE!",
-                Style::default().fg(Color::LightGreen).bold(),
+                format!("Rounds Played: {}", self.rounds),
+                Style::default().fg(Color::Magenta),
             )),
-        );
-    }
-    if stats.lives == 0 {
+            Line::from(Span::styled(
+                format!("Max Streak: {}", self.max_streak),
+                Style::default().fg(Color::Cyan),
+            )),
+        ]);
+        if self.new_high_score_reached {
+            lines.insert(
+                1,
+                Line::from(Span::styled(
+                    "NEW HIGH SCORE!",
+                    Style::default().fg(Color::LightGreen).bold(),
+                )),
+            );
+        }
+        if self.lives == 0 {
+            lines.push(Line::from(Span::styled(
+                "You lost all your lives.",
+                Style::default().fg(Color::Red),
+            )));
+        }
+        lines.extend(self.game_mode_summary());
         lines.push(Line::from(Span::styled(
-            "You lost all your lives.",
-            Style::default().fg(Color::Red),
+            "Press Enter to restart or Esc to exit",
+            Style::default().fg(Color::Yellow),
         )));
+        #[allow(clippy::cast_possible_truncation)]
+        let [rows] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+            .flex(Flex::Center)
+            .areas(combined_rect);
+        Paragraph::new(lines).alignment(Center).render(rows, buf);
+    }
+
+    /// Game over lines that only some game modes have
+    fn game_mode_summary(&self) -> Vec<Line<'static>> {
+        match self.config.game_mode {
+            GameMode::Classic | GameMode::Zen | GameMode::Mixed => vec![],
+            GameMode::Daily { day } => {
+                let verdict = if self.scored {
+                    "Today's score is on the board".to_string()
+                } else {
+                    format!("Not scored, today's {} stands", self.prev_high_score_for_display)
+                };
+                let share_lines = daily::share_lines(
+                    &daily::date_of_day(day),
+                    &self.round_results,
+                    self.time_taken,
+                );
+                let mut lines = vec![
+                    Line::from(Span::styled(verdict, Style::default().fg(Color::Yellow))),
+                    Line::from(""),
+                ];
+                lines.extend(
+                    share_lines.into_iter().map(|line| {
+                        Line::from(Span::styled(line, Style::default().fg(Color::White)))
+                    }),
+                );
+                lines.push(Line::from(""));
+                lines
+            },
+            GameMode::TimeAttack => {
+                let (correct, _) = self.accuracy();
+                #[allow(clippy::cast_precision_loss)]
+                let per_minute = if self.time_taken > 0.0 {
+                    correct as f64 * 60.0 / self.time_taken
+                } else {
+                    0.0
+                };
+                vec![Line::from(Span::styled(
+                    format!("Answers per Minute: {per_minute:.1}"),
+                    Style::default().fg(Color::LightBlue),
+                ))]
+            },
+            GameMode::Study => {
+                let due_tomorrow = self.study.as_ref().map_or(0, flashcards::Session::due_tomorrow);
+                let verdict = if self.rounds == 0 {
+                    "No cards due today".to_string()
+                } else {
+                    format!("Cards reviewed: {}", self.rounds)
+                };
+                vec![
+                    Line::from(Span::styled(verdict, Style::default().fg(Color::LightBlue))),
+                    Line::from(Span::styled(
+                        format!("Due tomorrow: {due_tomorrow}"),
+                        Style::default().fg(Color::LightBlue),
+                    )),
+                ]
+            },
+        }
     }
-    lines.push(Line::from(Span::styled(
-        "Press Enter to restart or Esc to exit",
-        Style::default().fg(Color::Yellow),
-    )));
-    Paragraph::new(lines)
-        .alignment(Center)
-        .render(center(combined_rect, Constraint::Length(48)), buf);
 }
 
-pub struct BinaryNumbersGame {
-    puzzle: BinaryNumbersPuzzle,
-    bits: Bits,
-    number_mode: NumberMode,
+impl BinaryNumbersPuzzle {
+    fn render_current_number(&self, area: Rect, buf: &mut Buffer) {
+        let block =
+            Block::bordered().border_type(Double).border_style(Style::default().dark_gray());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let lines: Vec<Line> = match self.conversion.prompt_base() {
+            Base::Binary | Base::Gray => {
+                // wide words wrap between groups when the terminal is too narrow
+                let binary = self.current_to_binary_string();
+                let mut lines: Vec<Line> = wrap_groups(&binary, usize::from(inner.width))
+                    .into_iter()
+                    .map(Line::from)
+                    .collect();
+                if let (Some(sfx), Some(last)) = (self.bits.scale_suffix(), lines.last_mut()) {
+                    last.push_span(Span::styled(sfx, Style::default().fg(Color::DarkGray)));
+                }
+                lines
+            },
+            base => vec![Line::from(self.format_value(self.suggestions[0], base))],
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let [rows] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+            .flex(Flex::Center)
+            .areas(inner);
+        Paragraph::new(lines).alignment(Center).render(rows, buf);
+    }
+
+    fn choices(&self) -> Choices {
+        Choices {
+            labels: self.listed.iter().map(|&s| self.format_answer(s)).collect(),
+            selected: self
+                .selected_suggestion
+                .and_then(|selected| self.listed.iter().position(|&s| s == selected)),
+            correct: self.listed.iter().position(|&s| s == self.suggestions[0]).unwrap_or(0),
+            result: self.round.guess_result,
+        }
+    }
+
+    fn render_entry(&self, area: Rect, buf: &mut Buffer) {
+        Block::bordered()
+            .border_type(BorderType::Double)
+            .fg(result_color(self.round.guess_result))
+            .render(area, buf);
+
+        let mut spans = vec![Span::styled(self.entry.clone(), Style::default().fg(Color::White))];
+        match self.round.guess_result {
+            None => spans.push(Span::styled("_", Style::default().fg(Color::LightCyan))),
+            Some(GuessResult::Correct) => {},
+            Some(GuessResult::Incorrect | GuessResult::Timeout) => {
+                let answer = self.format_answer(self.suggestions[0]);
+                spans.push(Span::styled("  answer: ", Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(answer, Style::default().fg(Color::LightGreen)));
+            },
+        }
+
+        let line = Line::from(spans);
+        #[allow(clippy::cast_possible_truncation)]
+        let width = line.width() as u16;
+        Paragraph::new(line).alignment(Center).render(center(area, Constraint::Length(width)), buf);
+    }
+
+    fn render_bit_builder(&self, area: Rect, buf: &mut Buffer) {
+        Block::bordered()
+            .border_type(BorderType::Double)
+            .fg(result_color(self.round.guess_result))
+            .render(area, buf);
+
+        let inner = Block::bordered().inner(area);
+        let lines = self.bit_builder_lines(inner.width);
+        #[allow(clippy::cast_possible_truncation)]
+        let [rows] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+            .flex(Flex::Center)
+            .areas(inner);
+        Paragraph::new(lines).alignment(Center).render(rows, buf);
+    }
+
+    /// The bit cells, followed by the answer after a miss. Wide words drop the spaces
+    /// between cells and put the answer on its own line when they run out of room.
+    fn bit_builder_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let num_bits = self.bits.to_int() as usize;
+        let group = self.group_size();
+        let spaced_width = 2 * num_bits - 1 + (num_bits - 1) / group;
+        let compact = spaced_width > usize::from(width);
+        let cells = match self.conversion.answer_base() {
+            Base::Permissions => permission_string(self.built_bits, self.bits.to_int()),
+            _ => format!("{:0num_bits$b}", self.built_bits),
+        };
+        let mut spans = Vec::new();
+        for (i, bit) in cells.chars().enumerate() {
+            if i > 0 && (num_bits - i).is_multiple_of(group) {
+                spans.push(Span::raw(if compact { " " } else { "  " }));
+            } else if i > 0 && !compact {
+                spans.push(Span::raw(" "));
+            }
+            let style = if self.round.guess_result.is_none() && i == self.cursor {
+                Style::default().fg(Color::Black).bg(Color::LightCyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            spans.push(Span::styled(bit.to_string(), style));
+        }
+        if let Some(sfx) = self.bits.scale_suffix() {
+            spans.push(Span::styled(sfx, Style::default().fg(Color::DarkGray)));
+        }
+        let mut lines = vec![Line::from(spans)];
+        if matches!(self.round.guess_result, Some(GuessResult::Incorrect | GuessResult::Timeout)) {
+            let answer = vec![
+                Span::styled("  answer: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    self.format_answer(self.suggestions[0]),
+                    Style::default().fg(Color::LightGreen),
+                ),
+            ];
+            let answer_width: usize = answer.iter().map(Span::width).sum();
+            if lines[0].width() + answer_width <= usize::from(width) {
+                lines[0].spans.extend(answer);
+            } else {
+                lines.push(Line::from(answer));
+            }
+        }
+        lines
+    }
+}
+
+/// Unscaled values that come out of the typical slips while reading the pattern `raw`:
+/// a single bit misread, the bits read back to front or with the low nibbles swapped,
+/// every bit weighted one power off, and in signed modes the sign bit misread
+fn near_misses(raw: u64, num_bits: u32, number_mode: NumberMode, rng: &mut impl Rng) -> Vec<i64> {
+    let mask = u64::MAX >> (64 - num_bits);
+    let mut patterns = vec![
+        raw ^ 1 << rng.random_range(0..num_bits),
+        raw ^ 1 << rng.random_range(0..num_bits),
+        raw.reverse_bits() >> (64 - num_bits),
+        (raw << 1) & mask,
+        raw >> 1,
+    ];
+    if num_bits >= 8 {
+        patterns.push((raw & !0xFF) | (raw & 0x0F) << 4 | (raw & 0xF0) >> 4);
+    }
+    let mut values: Vec<i64> =
+        patterns.into_iter().map(|pattern| number_mode.value_of(pattern, num_bits)).collect();
+    if number_mode.is_signed() {
+        let value = number_mode.value_of(raw, num_bits);
+        values.push(-value);
+        // the sign bit read as a plain digit, or as in another signed encoding
+        values.extend(
+            NumberMode::ALL
+                .iter()
+                .filter(|&&mode| mode != number_mode)
+                .map(|mode| mode.value_of(raw, num_bits)),
+        );
+    }
+    values.shuffle(rng);
+    values
+}
+
+/// Break a grouped bit string between groups, so no line is wider than `width`
+fn wrap_groups(bits: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for group in bits.split(' ') {
+        match lines.last_mut() {
+            Some(line) if line.len() + 1 + group.len() <= width => {
+                line.push(' ');
+                line.push_str(group);
+            },
+            _ => lines.push(group.to_string()),
+        }
+    }
+    lines
+}
+
+fn hotkey_span<'a>(key: &'a str, description: &str) -> Vec<Span<'a>> {
+    vec![
+        Span::styled("<", Style::default().fg(Color::White)),
+        Span::styled(key, Style::default().fg(Color::LightCyan)),
+        Span::styled(format!("> {description}"), Style::default().fg(Color::White)),
+    ]
+}
+
+pub struct BinaryNumbersGame<P: Puzzle = BinaryNumbersPuzzle> {
+    puzzle: P,
+    config: PuzzleConfig,
     exit_intended: bool,
     score: u32,
     streak: u32,
@@ -382,6 +604,20 @@ pub struct BinaryNumbersGame {
     high_scores: HighScores,
     prev_high_score_for_display: u32,
     new_high_score_reached: bool,
+    rng: ChaCha8Rng,
+    /// False for practice and for replays of a daily challenge that was already played today
+    scored: bool,
+    /// Whether each round was answered correctly, for the daily share grid and practice accuracy
+    round_results: Vec<bool>,
+    /// Seconds spent on the rounds so far
+    time_taken: f64,
+    /// One countdown over all rounds of a time attack, in place of the timer of each round
+    countdown: Option<Round>,
+    /// Outcome and points of the last answer of a time attack, which moves on without a pause
+    previous_outcome: Option<(GuessResult, u32)>,
+    weaknesses: Weaknesses,
+    /// The cards left to review in a study game
+    study: Option<flashcards::Session>,
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -392,17 +628,37 @@ enum GameState {
     GameOver,
 }
 
-impl MainScreenWidget for BinaryNumbersGame {
+/// Start a game made of the kind of puzzle chosen in the config
+pub fn new_game(config: PuzzleConfig) -> Box<dyn MainScreenWidget> {
+    match config.kind {
+        PuzzleKind::Conversion => Box::new(BinaryNumbersGame::<BinaryNumbersPuzzle>::new(config)),
+        PuzzleKind::Bitwise => Box::new(BinaryNumbersGame::<BitwisePuzzle>::new(config)),
+        PuzzleKind::Arithmetic => Box::new(BinaryNumbersGame::<ArithmeticPuzzle>::new(config)),
+        PuzzleKind::HalfFloat => Box::new(BinaryNumbersGame::<HalfFloatPuzzle>::new(config)),
+        PuzzleKind::FixedPoint => Box::new(BinaryNumbersGame::<FixedPointPuzzle>::new(config)),
+        PuzzleKind::Bcd => Box::new(BinaryNumbersGame::<BcdPuzzle>::new(config)),
+    }
+}
+
+impl<P: Puzzle> MainScreenWidget for BinaryNumbersGame<P> {
     fn run(&mut self, dt: f64) {
-        self.refresh_stats_snapshot();
         if self.game_state == GameState::GameOver {
             return;
         }
-        self.puzzle.run(dt);
-        if self.puzzle.guess_result.is_some() && !self.puzzle_resolved {
+        if let Some(countdown) = &mut self.countdown {
+            let time_left = countdown.time_left;
+            countdown.run(dt);
+            self.time_taken += time_left - countdown.time_left.max(0.0);
+            if countdown.guess_result.is_some() {
+                self.game_state = GameState::GameOver;
+                return;
+            }
+        } else if self.config.game_mode.has_round_timer() {
+            self.puzzle.round_mut().run(dt);
+        }
+        if self.puzzle.round().guess_result.is_some() && !self.puzzle_resolved {
             self.finalize_round();
         }
-        self.refresh_stats_snapshot();
     }
 
     fn handle_input(&mut self, input: KeyEvent) {
@@ -411,58 +667,99 @@ impl MainScreenWidget for BinaryNumbersGame {
     fn is_exit_intended(&self) -> bool {
         self.exit_intended
     }
+    fn is_active(&self) -> bool {
+        self.game_state == GameState::Active
+    }
 }
 
-impl BinaryNumbersGame {
-    pub fn new(bits: Bits, number_mode: NumberMode) -> Self {
-        Self::new_with_max_lives(bits, number_mode, 3)
+impl<P: Puzzle> BinaryNumbersGame<P> {
+    pub fn new(config: PuzzleConfig) -> Self {
+        // endless games never lose a life, so any count does
+        let max_lives = config.lives.count().unwrap_or(3);
+        Self::new_with_max_lives(config, max_lives)
     }
-    pub fn new_with_max_lives(bits: Bits, number_mode: NumberMode, max_lives: u32) -> Self {
+    pub fn new_with_max_lives(mut config: PuzzleConfig, max_lives: u32) -> Self {
         let hs = HighScores::load();
-        let high_score_key = Self::compute_high_score_key(&bits, number_mode);
-        let starting_prev = hs.get(&high_score_key);
-        let mut game = Self {
-            bits: bits.clone(),
-            number_mode,
-            puzzle: Self::init_puzzle(bits, number_mode, 0),
+        let starting_prev = hs.recorded_score(&config);
+        let scored = hs.is_scored(&config);
+        let mut rng = config.rng();
+        let countdown = config.game_mode.time_limit().map(Round::with_time);
+        let weaknesses = Weaknesses::load();
+        let mut study = (config.game_mode == GameMode::Study).then(flashcards::Session::start);
+        if let Some(study) = &mut study {
+            study.next_card(&mut config);
+        }
+        Self {
+            puzzle: Self::init_puzzle(&mut config, 0, &mut rng, &weaknesses),
+            config,
             exit_intended: false,
             score: 0,
             streak: 0,
             rounds: 0,
             puzzle_resolved: false,
-            lives: max_lives.min(3),
+            lives: max_lives,
             max_lives,
-            game_state: GameState::Active,
+            game_state: Self::starting_state(study.as_ref()),
             max_streak: 0,
             high_scores: hs,
             prev_high_score_for_display: starting_prev,
             new_high_score_reached: false,
-        };
-        // Initialize stats snapshot immediately so stats display on first render
-        game.refresh_stats_snapshot();
-        game
+            rng,
+            scored,
+            round_results: Vec::new(),
+            time_taken: 0.0,
+            countdown,
+            previous_outcome: None,
+            weaknesses,
+            study,
+        }
     }
 
-    pub fn init_puzzle(bits: Bits, number_mode: NumberMode, streak: u32) -> BinaryNumbersPuzzle {
-        BinaryNumbersPuzzle::new(bits, number_mode, streak)
+    /// A study game with no cards due is over before it starts
+    fn starting_state(study: Option<&flashcards::Session>) -> GameState {
+        if study.is_some_and(|study| study.total() == 0) {
+            GameState::GameOver
+        } else {
+            GameState::Active
+        }
     }
 
-    fn compute_high_score_key(bits: &Bits, number_mode: NumberMode) -> String {
-        let bits_key = bits.high_score_key();
-        let mode_suffix = match number_mode {
-            NumberMode::Unsigned => "u",
-            NumberMode::Signed => "s",
-        };
-        format!("{}{}", bits_key, mode_suffix)
+    /// The next puzzle, of a newly drawn width and number mode in mixed games
+    pub fn init_puzzle(
+        config: &mut PuzzleConfig,
+        streak: u32,
+        rng: &mut ChaCha8Rng,
+        weaknesses: &Weaknesses,
+    ) -> P {
+        if config.game_mode == GameMode::Mixed {
+            (config.bits, config.number_mode) = mixed::pick(streak, rng);
+        }
+        if config.is_adaptive() {
+            config.weak_spots = weaknesses.spots(&config.weakness_key());
+        }
+        P::new(config, streak, rng)
     }
 
-    pub fn is_active(&self) -> bool {
-        self.game_state == GameState::Active
+    /// Move on to a new puzzle, the next due card in study games
+    fn next_puzzle(&mut self) {
+        if let Some(study) = &mut self.study {
+            study.next_card(&mut self.config);
+        }
+        self.puzzle =
+            Self::init_puzzle(&mut self.config, self.streak, &mut self.rng, &self.weaknesses);
+    }
+
+    /// Rounds after which the game is over, which for a study game are the cards due
+    fn round_limit(&self) -> Option<u32> {
+        self.study.as_ref().map(flashcards::Session::total).or(self.config.game_mode.round_limit())
     }
 }
 
-impl BinaryNumbersGame {
+impl<P: Puzzle> BinaryNumbersGame<P> {
     pub fn lives_hearts(&self) -> String {
+        if self.config.lives == Lives::Endless {
+            return "∞".to_string();
+        }
         let full_count = self.lives.min(self.max_lives) as usize;
         let full = "♥".repeat(full_count);
         let empty_count = self.max_lives.saturating_sub(self.lives) as usize;
@@ -471,8 +768,19 @@ impl BinaryNumbersGame {
     }
 
     fn finalize_round(&mut self) {
-        if let Some(result) = self.puzzle.guess_result {
+        if let Some(result) = self.puzzle.round().guess_result {
             self.rounds += 1;
+            let round = self.puzzle.round();
+            self.time_taken += round.time_total - round.time_left.max(0.0);
+            self.round_results.push(result == GuessResult::Correct);
+            self.record_weakness();
+            if let Some(study) = &mut self.study {
+                let round = self.puzzle.round();
+                let share_left = round.time_left.max(0.0) / round.time_total;
+                let correct = result == GuessResult::Correct;
+                let timed_out = result == GuessResult::Timeout;
+                study.review(flashcards::quality(correct, timed_out, share_left));
+            }
             match result {
                 GuessResult::Correct => {
                     self.streak += 1;
@@ -480,35 +788,61 @@ impl BinaryNumbersGame {
                         self.max_streak = self.streak;
                     }
                     let streak_bonus = (self.streak - 1) * 2;
-                    let points = 10 + streak_bonus;
+                    let points = self.config.base_points() + streak_bonus;
                     self.score += points;
-                    self.puzzle.last_points_awarded = points;
+                    self.puzzle.round_mut().last_points_awarded = points;
                     if self.streak.is_multiple_of(5) && self.lives < self.max_lives {
                         self.lives += 1;
                     }
                 },
                 GuessResult::Incorrect | GuessResult::Timeout => {
                     self.streak = 0;
-                    self.puzzle.last_points_awarded = 0;
-                    if self.lives > 0 {
+                    self.puzzle.round_mut().last_points_awarded = 0;
+                    if self.lives > 0 && self.config.loses_lives() {
                         self.lives -= 1;
                     }
+                    // a skip costs as much as a wrong answer, or skipping would be free
+                    if let Some(countdown) = &mut self.countdown {
+                        countdown.time_left -= GameMode::TIME_PENALTY;
+                    }
                 },
             }
-            // high score update
-            let bits_key = Self::compute_high_score_key(&self.bits, self.number_mode);
-            let prev = self.high_scores.get(&bits_key);
-            if self.score > prev {
-                if !self.new_high_score_reached {
-                    self.prev_high_score_for_display = prev;
-                }
-                self.high_scores.update(&bits_key, self.score);
-                self.new_high_score_reached = true;
-                let _ = self.high_scores.save();
+            match self.config.game_mode {
+                GameMode::Classic | GameMode::TimeAttack | GameMode::Mixed => {
+                    // high score update
+                    let bits_key = self.config.high_score_key();
+                    let prev = self.high_scores.get(&bits_key);
+                    if self.score > prev {
+                        if !self.new_high_score_reached {
+                            self.prev_high_score_for_display = prev;
+                        }
+                        self.high_scores.update(&bits_key, self.score);
+                        self.new_high_score_reached = true;
+                        let _ = self.high_scores.save();
+                    }
+                },
+                // recorded from the first answer on, so quitting halfway still uses up the day
+                GameMode::Daily { day } => {
+                    if self.scored {
+                        self.high_scores.record_daily(&daily::date_of_day(day), self.score);
+                        let _ = self.high_scores.save();
+                    }
+                },
+                // practice leaves the high scores alone
+                GameMode::Zen | GameMode::Study => {},
             }
+            let all_rounds_played = self.round_limit().is_some_and(|limit| self.rounds >= limit);
+            let out_of_time = self.countdown.as_ref().map(|countdown| countdown.time_left <= 0.0);
             // set state after round resolution
-            if self.lives == 0 {
+            if self.lives == 0 || all_rounds_played {
                 self.game_state = GameState::PendingGameOver; // defer summary until Enter
+            } else if out_of_time == Some(true) {
+                self.game_state = GameState::GameOver;
+            } else if out_of_time == Some(false) {
+                // a time attack goes straight on to the next puzzle
+                self.previous_outcome = Some((result, self.puzzle.round().last_points_awarded));
+                self.next_puzzle();
+                return;
             } else {
                 self.game_state = GameState::Result;
             }
@@ -516,6 +850,16 @@ impl BinaryNumbersGame {
         }
     }
 
+    /// Learn from the round which values and bits to ask more often
+    fn record_weakness(&mut self) {
+        if let Some(attempt) = self.puzzle.attempt()
+            && self.config.is_adaptive()
+        {
+            self.weaknesses.record(&self.config.weakness_key(), attempt);
+            let _ = self.weaknesses.save();
+        }
+    }
+
     pub fn handle_game_input(&mut self, input: KeyEvent) {
         if keybinds::is_exit(input) {
             self.exit_intended = true;
@@ -526,7 +870,7 @@ impl BinaryNumbersGame {
             self.handle_game_over_input(input);
             return;
         }
-        match self.puzzle.guess_result {
+        match self.puzzle.round().guess_result {
             None => self.handle_no_result_yet(input),
             Some(_) => self.handle_result_available(input),
         }
@@ -548,65 +892,61 @@ impl BinaryNumbersGame {
         self.score = 0;
         self.streak = 0;
         self.rounds = 0;
-        self.lives = self.max_lives.min(3);
+        self.lives = self.max_lives;
         self.game_state = GameState::Active;
         self.max_streak = 0;
-        let high_score_key = Self::compute_high_score_key(&self.bits, self.number_mode);
-        self.prev_high_score_for_display = self.high_scores.get(&high_score_key);
+        self.prev_high_score_for_display = self.high_scores.recorded_score(&self.config);
         self.new_high_score_reached = false;
-        self.puzzle = Self::init_puzzle(self.bits.clone(), self.number_mode, 0);
+        self.scored = self.high_scores.is_scored(&self.config);
+        self.round_results.clear();
+        self.time_taken = 0.0;
+        self.countdown = self.config.game_mode.time_limit().map(Round::with_time);
+        self.previous_outcome = None;
+        // a seeded game starts over with the same numbers
+        self.rng = self.config.rng();
+        if self.study.is_some() {
+            self.study = Some(flashcards::Session::start());
+            self.game_state = Self::starting_state(self.study.as_ref());
+        }
+        self.next_puzzle();
         self.puzzle_resolved = false;
-        self.refresh_stats_snapshot();
+    }
+
+    fn skip_puzzle(&mut self) {
+        // Skip puzzle counts as timeout
+        self.puzzle.round_mut().guess_result = Some(GuessResult::Timeout);
+        self.finalize_round();
     }
 
     fn handle_no_result_yet(&mut self, input: KeyEvent) {
-        match input {
-            x if keybinds::is_right(x) => {
-                // select the next suggestion
-                if let Some(selected) = self.puzzle.selected_suggestion {
-                    let current_index = self.puzzle.suggestions.iter().position(|&x| x == selected);
-                    if let Some(index) = current_index {
-                        let next_index = (index + 1) % self.puzzle.suggestions.len();
-                        self.puzzle.selected_suggestion = Some(self.puzzle.suggestions[next_index]);
-                    }
-                } else {
-                    // if no suggestion is selected, select the first one
-                    self.puzzle.selected_suggestion = Some(self.puzzle.suggestions[0]);
-                }
-            },
-            x if keybinds::is_left(x) => {
-                // select the previous suggestion
-                if let Some(selected) = self.puzzle.selected_suggestion {
-                    let current_index = self.puzzle.suggestions.iter().position(|&x| x == selected);
-                    if let Some(index) = current_index {
-                        let prev_index = if index == 0 {
-                            self.puzzle.suggestions.len() - 1
-                        } else {
-                            index - 1
-                        };
-                        self.puzzle.selected_suggestion = Some(self.puzzle.suggestions[prev_index]);
-                    }
-                }
-            },
-            x if keybinds::is_select(x) => {
-                if let Some(selected) = self.puzzle.selected_suggestion {
-                    if self.puzzle.is_correct_guess(selected) {
-                        self.puzzle.guess_result = Some(GuessResult::Correct);
-                    } else {
-                        self.puzzle.guess_result = Some(GuessResult::Incorrect);
-                    }
-                    self.finalize_round();
-                }
-            },
-            KeyEvent { code: KeyCode::Char('s' | 'S'), .. } => {
-                // Skip puzzle counts as timeout
-                self.puzzle.guess_result = Some(GuessResult::Timeout);
-                self.finalize_round();
-            },
-            _ => {},
+        if let KeyEvent { code: KeyCode::Char('s' | 'S'), .. } = input {
+            self.skip_puzzle();
+            return;
+        }
+        if let KeyEvent { code: KeyCode::Char('?'), .. } = input
+            && self.config.game_mode == GameMode::Zen
+        {
+            self.reveal_hint();
+            return;
+        }
+        self.puzzle.handle_answer_input(input);
+        if self.puzzle.round().guess_result.is_some() {
+            self.finalize_round();
         }
     }
 
+    fn reveal_hint(&mut self) {
+        let available = self.puzzle.hints().len();
+        let round = self.puzzle.round_mut();
+        round.hints_shown = (round.hints_shown + 1).min(available);
+    }
+
+    /// Rounds answered correctly and rounds played
+    fn accuracy(&self) -> (usize, usize) {
+        let correct = self.round_results.iter().filter(|&&correct| correct).count();
+        (correct, self.round_results.len())
+    }
+
     fn handle_result_available(&mut self, key: KeyEvent) {
         match key {
             x if keybinds::is_select(x) => {
@@ -617,8 +957,7 @@ impl BinaryNumbersGame {
                     },
                     GameState::Result => {
                         // start next puzzle
-                        self.puzzle =
-                            Self::init_puzzle(self.bits.clone(), self.number_mode, self.streak);
+                        self.next_puzzle();
                         self.puzzle_resolved = false;
                         self.game_state = GameState::Active;
                     },
@@ -630,241 +969,1320 @@ impl BinaryNumbersGame {
             _ => {},
         }
     }
-
-    fn refresh_stats_snapshot(&mut self) {
-        self.puzzle.stats_snapshot = Some(StatsSnapshot {
-            score: self.score,
-            streak: self.streak,
-            max_streak: self.max_streak,
-            rounds: self.rounds,
-            lives: self.lives,
-            bits: self.bits.clone(),
-            number_mode: self.number_mode,
-            hearts: self.lives_hearts(),
-            game_state: self.game_state,
-            prev_high_score: self.prev_high_score_for_display,
-            new_high_score: self.new_high_score_reached,
-        });
-    }
 }
 
+/// The input widget a puzzle is answered with, derived from its answer mode and notation
 #[derive(PartialEq, Copy, Clone, Debug)]
-enum GuessResult {
-    Correct,
-    Incorrect,
-    Timeout,
+enum AnswerInput {
+    Choice,
+    Typed,
+    BitBuilder,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum Bits {
     Four,
     FourShift4,
     FourShift8,
     FourShift12,
     Eight,
+    Nine,
     Twelve,
     Sixteen,
+    TwentyFour,
+    ThirtyTwo,
+    Custom(CustomBits),
 }
 
 impl Bits {
+    /// Every width except the custom one, in order of difficulty
+    pub const PRESETS: [Self; 10] = [
+        Self::Four,
+        Self::FourShift4,
+        Self::FourShift8,
+        Self::FourShift12,
+        Self::Eight,
+        Self::Nine,
+        Self::Twelve,
+        Self::Sixteen,
+        Self::TwentyFour,
+        Self::ThirtyTwo,
+    ];
+
     pub const fn to_int(&self) -> u32 {
         match self {
             Self::Four | Self::FourShift4 | Self::FourShift8 | Self::FourShift12 => 4,
             Self::Eight => 8,
+            Self::Nine => 9,
             Self::Twelve => 12,
             Self::Sixteen => 16,
+            Self::TwentyFour => 24,
+            Self::ThirtyTwo => 32,
+            Self::Custom(custom) => custom.width,
         }
     }
-    pub const fn scale_factor(&self) -> u32 {
+    /// How many bits the raw pattern is shifted left by the scale factor
+    pub const fn scale_shift(&self) -> u32 {
         match self {
-            Self::Four => 1,
-            Self::FourShift4 => 16,
-            Self::FourShift8 => 256,
-            Self::FourShift12 => 4096,
-            Self::Eight => 1,
-            Self::Twelve => 1,
-            Self::Sixteen => 1,
+            Self::FourShift4 => 4,
+            Self::FourShift8 => 8,
+            Self::FourShift12 => 12,
+            Self::Custom(custom) => custom.shift,
+            _ => 0,
         }
     }
-    pub const fn high_score_key(&self) -> u32 {
+    pub const fn scale_factor(&self) -> u64 {
+        1 << self.scale_shift()
+    }
+    /// Width followed by the shift, e.g. "8" or "412". Custom widths are marked as such,
+    /// as "custom12.4.5" could otherwise clash with a preset.
+    pub fn high_score_key(&self) -> String {
+        let shift = match self.scale_shift() {
+            0 => String::new(),
+            shift => shift.to_string(),
+        };
         match self {
-            Self::Four => 4,
-            Self::FourShift4 => 44,
-            Self::FourShift8 => 48,
-            Self::FourShift12 => 412,
-            Self::Eight => 8,
-            Self::Twelve => 12,
-            Self::Sixteen => 16,
+            Self::Custom(custom) => {
+                format!("custom{}.{}.{}", custom.width, custom.shift, custom.suggestions)
+            },
+            _ => format!("{}{shift}", self.to_int()),
         }
     }
-    pub const fn upper_bound(&self) -> u32 {
-        (u32::pow(2, self.to_int()) - 1) * self.scale_factor()
+    pub const fn upper_bound(&self) -> u64 {
+        self.mask() * self.scale_factor()
+    }
+    /// All bits of the (unscaled) word set
+    pub const fn mask(&self) -> u64 {
+        (1u64 << self.to_int()) - 1
     }
     pub const fn suggestion_count(&self) -> usize {
         match self {
             Self::Four | Self::FourShift4 | Self::FourShift8 | Self::FourShift12 => 3,
-            Self::Eight => 4,
+            Self::Eight | Self::Nine => 4,
             Self::Twelve => 5,
-            Self::Sixteen => 6,
+            Self::Sixteen | Self::TwentyFour | Self::ThirtyTwo => 6,
+            Self::Custom(custom) => custom.suggestions,
         }
     }
-    pub const fn label(&self) -> &'static str {
-        match self {
-            Self::Four => "4 bit",
-            Self::FourShift4 => "4 bit*16",
-            Self::FourShift8 => "4 bit*256",
-            Self::FourShift12 => "4 bit*4096",
-            Self::Eight => "8 bit",
-            Self::Twelve => "12 bit",
-            Self::Sixteen => "16 bit",
+    /// e.g. "8 bit" or "4 bit*16"
+    pub fn label(&self) -> String {
+        match self.scale_shift() {
+            0 => format!("{} bit", self.to_int()),
+            _ => format!("{} bit*{}", self.to_int(), self.scale_factor()),
+        }
+    }
+    pub fn scale_suffix(&self) -> Option<String> {
+        match self.scale_shift() {
+            0 => None,
+            _ => Some(format!(" x{}", self.scale_factor())),
         }
     }
+    /// Hex digits needed for the scaled pattern, so 4 bit*256 shows as e.g. 0xA00
+    pub const fn hex_digits(&self) -> usize {
+        (self.to_int() + self.scale_shift()).div_ceil(4) as usize
+    }
+    pub const fn octal_digits(&self) -> usize {
+        (self.to_int() + self.scale_shift()).div_ceil(3) as usize
+    }
+    /// Raw (unscaled) bit pattern of a scaled value in the encoding of the number mode
+    pub const fn raw_bits(&self, value: i64, number_mode: NumberMode) -> u64 {
+        let unscaled = value / self.scale_factor() as i64;
+        number_mode.raw_of(unscaled, self.to_int())
+    }
 }
 
-pub struct BinaryNumbersPuzzle {
-    bits: Bits,
-    #[allow(dead_code)]
-    number_mode: NumberMode,
-    #[allow(dead_code)]
-    current_number: u32, // scaled value used for suggestions matching
-    raw_current_number: u32, // raw bit value (unscaled) for display
-    suggestions: Vec<i32>,   // Changed to i32 to support signed values
-    selected_suggestion: Option<i32>,
-    time_total: f64,
-    time_left: f64,
-    guess_result: Option<GuessResult>,
-    last_points_awarded: u32,
-    stats_snapshot: Option<StatsSnapshot>,
-    skip_first_dt: bool, // Skip first dt to prevent timer jump when starting new puzzle
+/// Width, shift and suggestion count picked by the player for the custom menu entry.
+/// The steppers keep the combination valid: the scaled pattern fits 32 bits
+/// and there are enough distinct numbers for the suggestions.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CustomBits {
+    pub width: u32,
+    pub shift: u32,
+    pub suggestions: usize,
 }
 
-impl BinaryNumbersPuzzle {
-    pub fn new(bits: Bits, number_mode: NumberMode, streak: u32) -> Self {
-        let mut rng = rand::rng();
+impl Default for CustomBits {
+    fn default() -> Self {
+        Self { width: 10, shift: 0, suggestions: 4 }
+    }
+}
 
-        let mut suggestions = Vec::new();
-        let scale = bits.scale_factor();
-        let num_bits = bits.to_int();
+impl CustomBits {
+    pub const MAX_WIDTH: u32 = 32;
+    pub const MAX_SUGGESTIONS: usize = 6;
+
+    /// The combination if it is one the steppers could have reached
+    pub fn new(width: u32, shift: u32, suggestions: usize) -> Option<Self> {
+        let custom = Self { width, shift, suggestions };
+        let valid = (1..=Self::MAX_WIDTH).contains(&width)
+            && suggestions >= 2
+            && custom.clamped() == custom;
+        valid.then_some(custom)
+    }
 
-        match number_mode {
-            NumberMode::Unsigned => {
-                while suggestions.len() < bits.suggestion_count() {
-                    let raw = rng.random_range(0..u32::pow(2, num_bits));
-                    let num = (raw * scale) as i32;
-                    if !suggestions.contains(&num) {
-                        suggestions.push(num);
-                    }
-                }
-            },
-            NumberMode::Signed => {
-                // For signed mode, use two's complement representation
-                // Range is from -(2^(n-1)) to 2^(n-1)-1
-                while suggestions.len() < bits.suggestion_count() {
-                    let raw = rng.random_range(0..u32::pow(2, num_bits));
-                    // Convert raw bits to signed value using two's complement
-                    let signed_val = if raw >= (1 << (num_bits - 1)) {
-                        // Negative number: raw - 2^n
-                        (raw as i32) - (1 << num_bits)
-                    } else {
-                        // Positive number
-                        raw as i32
-                    };
-                    let num = signed_val * (scale as i32);
-                    if !suggestions.contains(&num) {
-                        suggestions.push(num);
-                    }
-                }
+    pub fn step_width(self, forward: bool) -> Self {
+        let width = step_wrapping(self.width, 1, Self::MAX_WIDTH, forward);
+        Self { width, ..self }.clamped()
+    }
+
+    pub fn step_shift(self, forward: bool) -> Self {
+        let shift = step_wrapping(self.shift, 0, Self::MAX_WIDTH - self.width, forward);
+        Self { shift, ..self }
+    }
+
+    pub fn step_suggestions(self, forward: bool) -> Self {
+        let suggestions = step_wrapping(self.suggestions, 2, self.max_suggestions(), forward);
+        Self { suggestions, ..self }
+    }
+
+    /// A 1 bit word only has two numbers to choose from
+    const fn max_suggestions(self) -> usize {
+        if self.width < 3 {
+            1 << self.width
+        } else {
+            Self::MAX_SUGGESTIONS
+        }
+    }
+
+    fn clamped(self) -> Self {
+        Self {
+            shift: self.shift.min(Self::MAX_WIDTH - self.width),
+            suggestions: self.suggestions.min(self.max_suggestions()),
+            ..self
+        }
+    }
+}
+
+/// One step up or down within `min..=max`, wrapping around at both ends
+pub fn step_wrapping<T>(value: T, min: T, max: T, forward: bool) -> T
+where
+    T: Copy + PartialOrd + std::ops::Add<Output = T> + std::ops::Sub<Output = T> + From<u8>,
+{
+    let one = T::from(1);
+    match (forward, value) {
+        (true, v) if v >= max => min,
+        (true, v) => v + one,
+        (false, v) if v <= min => max,
+        (false, v) => v - one,
+    }
+}
+
+/// The notation a number is written in, either as the prompt or as an answer
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Base {
+    Binary,
+    Decimal,
+    Hex,
+    Octal,
+    /// Unix permission triplets like rwxr-x---
+    Permissions,
+    /// Reflected binary code, neighbouring numbers differ in a single bit
+    Gray,
+}
+
+/// Render a bit pattern as rwx triplets, with '-' for every cleared bit
+fn permission_string(raw_bits: u64, num_bits: u32) -> String {
+    (0..num_bits)
+        .rev()
+        .map(|bit| match (raw_bits >> bit & 1, bit % 3) {
+            (0, _) => '-',
+            (_, 2) => 'r',
+            (_, 1) => 'w',
+            _ => 'x',
+        })
+        .collect()
+}
+
+/// Reflected binary Gray code of a bit pattern
+pub const fn gray_encode(raw_bits: u64) -> u64 {
+    raw_bits ^ (raw_bits >> 1)
+}
+
+/// The bit pattern a reflected binary Gray code stands for
+pub const fn gray_decode(mut gray: u64) -> u64 {
+    let mut shift = 1;
+    while shift < u64::BITS {
+        gray ^= gray >> shift;
+        shift <<= 1;
+    }
+    gray
+}
+
+/// Which notation is shown as the prompt and which notation the answers are given in
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Conversion {
+    #[default]
+    BinaryToDecimal,
+    DecimalToBinary,
+    BinaryToHex,
+    HexToBinary,
+    HexToDecimal,
+    BinaryToOctal,
+    OctalToBinary,
+    GrayToBinary,
+    BinaryToGray,
+    GrayToDecimal,
+    DecimalToGray,
+    PermissionsToOctal,
+    OctalToPermissions,
+}
+
+impl Conversion {
+    /// All conversions in the order the start menu cycles through them
+    pub const ALL: [Self; 13] = [
+        Self::BinaryToDecimal,
+        Self::DecimalToBinary,
+        Self::BinaryToHex,
+        Self::HexToBinary,
+        Self::HexToDecimal,
+        Self::BinaryToOctal,
+        Self::OctalToBinary,
+        Self::GrayToBinary,
+        Self::BinaryToGray,
+        Self::GrayToDecimal,
+        Self::DecimalToGray,
+        Self::PermissionsToOctal,
+        Self::OctalToPermissions,
+    ];
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::BinaryToDecimal => "BIN → DEC",
+            Self::DecimalToBinary => "DEC → BIN",
+            Self::BinaryToHex => "BIN → HEX",
+            Self::HexToBinary => "HEX → BIN",
+            Self::HexToDecimal => "HEX → DEC",
+            Self::BinaryToOctal => "BIN → OCT",
+            Self::OctalToBinary => "OCT → BIN",
+            Self::GrayToBinary => "GRAY → BIN",
+            Self::BinaryToGray => "BIN → GRAY",
+            Self::GrayToDecimal => "GRAY → DEC",
+            Self::DecimalToGray => "DEC → GRAY",
+            Self::PermissionsToOctal => "RWX → OCT",
+            Self::OctalToPermissions => "OCT → RWX",
+        }
+    }
+    pub const fn prompt_base(&self) -> Base {
+        match self {
+            Self::BinaryToDecimal
+            | Self::BinaryToHex
+            | Self::BinaryToOctal
+            | Self::BinaryToGray => Base::Binary,
+            Self::DecimalToBinary | Self::DecimalToGray => Base::Decimal,
+            Self::HexToBinary | Self::HexToDecimal => Base::Hex,
+            Self::OctalToBinary | Self::OctalToPermissions => Base::Octal,
+            Self::PermissionsToOctal => Base::Permissions,
+            Self::GrayToBinary | Self::GrayToDecimal => Base::Gray,
+        }
+    }
+    pub const fn answer_base(&self) -> Base {
+        match self {
+            Self::BinaryToDecimal | Self::HexToDecimal | Self::GrayToDecimal => Base::Decimal,
+            Self::DecimalToBinary
+            | Self::HexToBinary
+            | Self::OctalToBinary
+            | Self::GrayToBinary => Base::Binary,
+            Self::BinaryToHex => Base::Hex,
+            Self::BinaryToOctal | Self::PermissionsToOctal => Base::Octal,
+            Self::OctalToPermissions => Base::Permissions,
+            Self::BinaryToGray | Self::DecimalToGray => Base::Gray,
+        }
+    }
+    pub const fn involves_octal(&self) -> bool {
+        matches!(self.prompt_base(), Base::Octal | Base::Permissions)
+            || matches!(self.answer_base(), Base::Octal | Base::Permissions)
+    }
+    /// Permission triplets only make sense for the 9 bit width
+    pub fn is_available_for(&self, bits: &Bits) -> bool {
+        match self {
+            Self::PermissionsToOctal | Self::OctalToPermissions => *bits == Bits::Nine,
+            _ => true,
+        }
+    }
+    /// Appended to the high score key, empty for the classic binary to decimal drill
+    pub const fn high_score_suffix(&self) -> &'static str {
+        match self {
+            Self::BinaryToDecimal => "",
+            Self::DecimalToBinary => "-d2b",
+            Self::BinaryToHex => "-b2h",
+            Self::HexToBinary => "-h2b",
+            Self::HexToDecimal => "-h2d",
+            Self::BinaryToOctal => "-b2o",
+            Self::OctalToBinary => "-o2b",
+            Self::GrayToBinary => "-g2b",
+            Self::BinaryToGray => "-b2g",
+            Self::GrayToDecimal => "-g2d",
+            Self::DecimalToGray => "-d2g",
+            Self::PermissionsToOctal => "-p2o",
+            Self::OctalToPermissions => "-o2p",
+        }
+    }
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// How the player gives their answer
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum AnswerMode {
+    #[default]
+    MultipleChoice,
+    /// Type the answer instead of picking it, so guessing by elimination is impossible
+    FreeEntry,
+}
+
+impl AnswerMode {
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::MultipleChoice => "MULTIPLE CHOICE",
+            Self::FreeEntry => "FREE ENTRY",
+        }
+    }
+    /// Shown after the mode label in the stats, empty for multiple choice
+    pub const fn short_label(&self) -> &'static str {
+        match self {
+            Self::MultipleChoice => "",
+            Self::FreeEntry => " FREE",
+        }
+    }
+    /// Points for a correct answer before the streak bonus
+    pub const fn base_points(&self) -> u32 {
+        match self {
+            Self::MultipleChoice => 10,
+            Self::FreeEntry => 20,
+        }
+    }
+    pub const fn high_score_suffix(&self) -> &'static str {
+        match self {
+            Self::MultipleChoice => "",
+            Self::FreeEntry => "-typed",
+        }
+    }
+    pub const fn next(self) -> Self {
+        match self {
+            Self::MultipleChoice => Self::FreeEntry,
+            Self::FreeEntry => Self::MultipleChoice,
+        }
+    }
+}
+
+/// How a game plays out, whatever its puzzles are
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum GameMode {
+    /// Rounds go on until the lives run out
+    #[default]
+    Classic,
+    /// The same fixed number of rounds for everyone on that day, scored once
+    Daily { day: u64 },
+    /// As many answers as possible before one countdown over all rounds runs out
+    TimeAttack,
+    /// Practice without timer or lives, with hints on request and nothing on the scoreboard
+    Zen,
+    /// Classic rounds of a random width and number mode each, with one high score for all
+    Mixed,
+    /// The flashcards due today, each scheduled again by how well it was known
+    Study,
+}
+
+impl GameMode {
+    /// Seconds taken off the countdown of a time attack for a wrong answer
+    pub const TIME_PENALTY: f64 = 5.0;
+
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Classic => "CLASSIC",
+            Self::Daily { .. } => "DAILY",
+            Self::TimeAttack => "TIME ATTACK",
+            Self::Zen => "ZEN",
+            Self::Mixed => "MIXED",
+            Self::Study => "STUDY",
+        }
+    }
+    /// Rounds after which the game is over, study games end with the cards that are due
+    pub const fn round_limit(&self) -> Option<u32> {
+        match self {
+            Self::Classic | Self::TimeAttack | Self::Zen | Self::Mixed | Self::Study => None,
+            Self::Daily { .. } => Some(daily::ROUNDS),
+        }
+    }
+    /// Seconds of the countdown that replaces the timer of each round
+    pub const fn time_limit(&self) -> Option<f64> {
+        match self {
+            Self::Classic | Self::Daily { .. } | Self::Zen | Self::Mixed | Self::Study => None,
+            Self::TimeAttack => Some(60.0),
+        }
+    }
+    /// Whether each round has a timer of its own that runs out
+    pub const fn has_round_timer(&self) -> bool {
+        match self {
+            // the time left on a card tells how well it is known
+            Self::Classic | Self::Daily { .. } | Self::Mixed | Self::Study => true,
+            Self::TimeAttack | Self::Zen => false,
+        }
+    }
+    /// Whether points are shown and count towards a high score, practice only counts answers
+    pub const fn keeps_score(&self) -> bool {
+        match self {
+            Self::Classic | Self::Daily { .. } | Self::TimeAttack | Self::Mixed => true,
+            Self::Zen | Self::Study => false,
+        }
+    }
+    /// Whether wrong answers cost lives and can end the game
+    pub const fn has_lives(&self) -> bool {
+        match self {
+            Self::Classic | Self::Mixed => true,
+            Self::Daily { .. } | Self::TimeAttack | Self::Zen | Self::Study => false,
+        }
+    }
+    /// What a wrong answer costs, shown in the status after the answer
+    pub fn wrong_answer_cost(&self) -> String {
+        match self {
+            Self::Classic | Self::Mixed => "lost a life".to_string(),
+            Self::Daily { .. } => "no points".to_string(),
+            Self::TimeAttack => format!("lost {} seconds", Self::TIME_PENALTY),
+            Self::Zen => "no harm done".to_string(),
+            Self::Study => "due again tomorrow".to_string(),
+        }
+    }
+    pub const fn high_score_suffix(&self) -> &'static str {
+        match self {
+            // daily scores are kept by date instead, and practice is not scored at all
+            Self::Classic | Self::Daily { .. } | Self::Zen | Self::Mixed | Self::Study => "",
+            Self::TimeAttack => "-60s",
+        }
+    }
+    /// The daily challenge, mixed and study games have their own menu entries,
+    /// so they are left out of the cycle
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Classic => Self::TimeAttack,
+            Self::TimeAttack => Self::Zen,
+            Self::Daily { .. } | Self::Zen | Self::Mixed | Self::Study => Self::Classic,
+        }
+    }
+}
+
+/// How many lives a game with lives starts with
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Lives {
+    /// The first miss ends the game
+    SuddenDeath,
+    #[default]
+    Classic,
+    /// Misses are never counted, the game goes on until the player leaves
+    Endless,
+}
+
+impl Lives {
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::SuddenDeath => "SUDDEN DEATH (1)",
+            Self::Classic => "CLASSIC (3)",
+            Self::Endless => "ENDLESS (∞)",
+        }
+    }
+    /// Lives at the start of a game and the most that streaks can win back, none if endless
+    pub const fn count(&self) -> Option<u32> {
+        match self {
+            Self::SuddenDeath => Some(1),
+            Self::Classic => Some(3),
+            Self::Endless => None,
+        }
+    }
+    /// Appended to the high score key, empty for classic lives to keep the existing scores
+    pub const fn high_score_suffix(&self) -> &'static str {
+        match self {
+            Self::SuddenDeath => "-1life",
+            Self::Classic => "",
+            Self::Endless => "-endless",
+        }
+    }
+    pub const fn next(self) -> Self {
+        match self {
+            Self::SuddenDeath => Self::Classic,
+            Self::Classic => Self::Endless,
+            Self::Endless => Self::SuddenDeath,
+        }
+    }
+}
+
+/// How the wrong suggestions of multiple choice conversions are picked
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum DistractorStrategy {
+    /// Random values of the same width
+    #[default]
+    Classic,
+    /// Near misses of the answer, so it can't be told apart by magnitude alone
+    Tricky,
+}
+
+impl DistractorStrategy {
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Classic => "CLASSIC",
+            Self::Tricky => "TRICKY",
+        }
+    }
+    /// Shown after the mode label in the stats, empty for classic
+    pub const fn short_label(&self) -> &'static str {
+        match self {
+            Self::Classic => "",
+            Self::Tricky => " TRICKY",
+        }
+    }
+    /// Points for a correct multiple choice answer before the streak bonus
+    pub const fn base_points(&self) -> u32 {
+        match self {
+            Self::Classic => AnswerMode::MultipleChoice.base_points(),
+            Self::Tricky => 15,
+        }
+    }
+    pub const fn high_score_suffix(&self) -> &'static str {
+        match self {
+            Self::Classic => "",
+            Self::Tricky => "-tricky",
+        }
+    }
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Classic => Self::Tricky,
+            Self::Tricky => Self::Classic,
+        }
+    }
+}
+
+/// What kind of puzzle a game is made of
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum PuzzleKind {
+    /// Convert a number from one notation to another
+    #[default]
+    Conversion,
+    /// Work out the result of a bit operator like AND, XOR or a shift
+    Bitwise,
+    /// Add or subtract, then tell which CPU flags the result sets
+    Arithmetic,
+    /// Decode an IEEE 754 half-precision pattern
+    HalfFloat,
+    /// Read a pattern with a binary point, like Q4.4
+    FixedPoint,
+    /// Packed binary-coded decimal, one nibble per digit
+    Bcd,
+}
+
+impl PuzzleKind {
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Conversion => "CONVERSION",
+            Self::Bitwise => "BITWISE",
+            Self::Arithmetic => "ARITHMETIC",
+            Self::HalfFloat => "HALF FLOAT",
+            Self::FixedPoint => "FIXED POINT",
+            Self::Bcd => "BCD",
+        }
+    }
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Conversion => Self::Bitwise,
+            Self::Bitwise => Self::Arithmetic,
+            Self::Arithmetic => Self::HalfFloat,
+            Self::HalfFloat => Self::FixedPoint,
+            Self::FixedPoint => Self::Bcd,
+            Self::Bcd => Self::Conversion,
+        }
+    }
+    /// Half-precision floats are exactly 16 bit wide, a binary point needs bits on both sides
+    /// and BCD whole unscaled nibbles
+    pub fn is_available_for(&self, bits: &Bits) -> bool {
+        match self {
+            Self::HalfFloat => *bits == Bits::Sixteen,
+            Self::FixedPoint => bits.to_int() > 1,
+            Self::Bcd => bits.to_int().is_multiple_of(4) && bits.scale_shift() == 0,
+            _ => true,
+        }
+    }
+    /// Bit operators and CPU flags only tell unsigned from two's complement words,
+    /// the other encodings would ask the very same puzzles as unsigned
+    pub const fn supports(&self, number_mode: NumberMode) -> bool {
+        match self {
+            Self::Bitwise | Self::Arithmetic => {
+                matches!(number_mode, NumberMode::Unsigned | NumberMode::Signed)
             },
+            _ => true,
         }
+    }
+}
 
-        // Shuffle suggestions
-        suggestions.shuffle(&mut rng);
+/// Everything chosen in the start menu that shapes the puzzles of a game
+#[derive(Clone, PartialEq, Debug)]
+pub struct PuzzleConfig {
+    pub bits: Bits,
+    pub number_mode: NumberMode,
+    pub kind: PuzzleKind,
+    pub conversion: Conversion,
+    pub answer_mode: AnswerMode,
+    pub distractors: DistractorStrategy,
+    /// Bits after the binary point of fixed point puzzles
+    pub fraction_bits: u32,
+    /// Replays the same numbers every game, drawn from the OS when not set
+    pub seed: Option<u64>,
+    pub game_mode: GameMode,
+    /// Only used by game modes with lives
+    pub lives: Lives,
+    /// Values and bits the player keeps missing at this width, to be asked more often
+    pub weak_spots: WeakSpots,
+    /// A pattern to ask about instead of a random one, like the card of a study game
+    pub target: Option<u64>,
+}
 
-        // Pick first suggestion as the current number
-        let current_number_signed = suggestions[0];
+impl PuzzleConfig {
+    pub fn new(bits: Bits, number_mode: NumberMode) -> Self {
+        Self {
+            fraction_bits: bits.to_int() / 2,
+            bits,
+            number_mode,
+            kind: PuzzleKind::default(),
+            conversion: Conversion::default(),
+            answer_mode: AnswerMode::default(),
+            distractors: DistractorStrategy::default(),
+            seed: None,
+            game_mode: GameMode::default(),
+            lives: Lives::default(),
+            weak_spots: WeakSpots::default(),
+            target: None,
+        }
+    }
+
+    /// Number modes the kind doesn't tell apart from unsigned become unsigned,
+    /// so they share its high score
+    pub const fn with_kind(mut self, kind: PuzzleKind) -> Self {
+        self.kind = kind;
+        if !kind.supports(self.number_mode) {
+            self.number_mode = NumberMode::Unsigned;
+        }
+        self
+    }
+
+    pub const fn with_conversion(mut self, conversion: Conversion) -> Self {
+        self.conversion = conversion;
+        self
+    }
+
+    pub const fn with_answer_mode(mut self, answer_mode: AnswerMode) -> Self {
+        self.answer_mode = answer_mode;
+        self
+    }
+
+    pub const fn with_fraction_bits(mut self, fraction_bits: u32) -> Self {
+        self.fraction_bits = fraction_bits;
+        self
+    }
+
+    pub const fn with_distractors(mut self, distractors: DistractorStrategy) -> Self {
+        self.distractors = distractors;
+        self
+    }
+
+    pub const fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub const fn with_game_mode(mut self, game_mode: GameMode) -> Self {
+        self.game_mode = game_mode;
+        self
+    }
 
-        // Calculate raw_current_number based on mode
-        let raw_current_number = match number_mode {
-            NumberMode::Unsigned => {
-                let current_number = current_number_signed.unsigned_abs();
-                current_number / scale
+    pub const fn with_lives(mut self, lives: Lives) -> Self {
+        self.lives = lives;
+        self
+    }
+
+    /// Whether puzzles target the weak spots of the player. Seeded games and the daily
+    /// challenge stay the same for everyone.
+    pub const fn is_adaptive(&self) -> bool {
+        self.seed.is_none() && !matches!(self.game_mode, GameMode::Daily { .. })
+    }
+
+    /// Weak spots are tracked by width and number mode, e.g. "8s"
+    pub fn weakness_key(&self) -> String {
+        format!("{}{}", self.bits.high_score_key(), self.number_mode.high_score_suffix())
+    }
+
+    /// Whether a wrong answer costs a life, which endless games never do
+    pub const fn loses_lives(&self) -> bool {
+        self.game_mode.has_lives() && self.lives.count().is_some()
+    }
+
+    /// The random numbers a game draws its puzzles from. ChaCha8 promises the same numbers
+    /// for a seed on every platform and version, unlike `StdRng`, so the daily challenge
+    /// and seeded games stay the same for everyone.
+    pub fn rng(&self) -> ChaCha8Rng {
+        self.seed.map_or_else(ChaCha8Rng::from_os_rng, ChaCha8Rng::seed_from_u64)
+    }
+
+    /// Distractors only exist when there are suggestions to pick from
+    const fn distractors_in_play(&self) -> DistractorStrategy {
+        match self.answer_mode {
+            AnswerMode::MultipleChoice => self.distractors,
+            AnswerMode::FreeEntry => DistractorStrategy::Classic,
+        }
+    }
+
+    /// Shown in the stats area, e.g. "8 bit SIGNED BIN → HEX FREE"
+    pub fn mode_label(&self) -> String {
+        match self.game_mode {
+            GameMode::Classic => self.puzzle_label(),
+            GameMode::Daily { day } => {
+                format!("DAILY {} {}", daily::date_of_day(day), self.puzzle_label())
             },
-            NumberMode::Signed => {
-                // For signed mode, we need to preserve the two's complement representation
-                // First, get the unscaled signed value
-                let unscaled_signed = current_number_signed / (scale as i32);
-
-                // Convert to unsigned bits using two's complement masking
-                // For n-bit number, mask is (2^n - 1)
-                let mask = (1u32 << num_bits) - 1;
-                (unscaled_signed as u32) & mask
+            GameMode::TimeAttack => format!("TIMED {}", self.puzzle_label()),
+            GameMode::Zen => format!("ZEN {}", self.puzzle_label()),
+            GameMode::Mixed => format!("MIXED {}", self.puzzle_label()),
+            GameMode::Study => format!("STUDY {}", self.puzzle_label()),
+        }
+    }
+
+    fn puzzle_label(&self) -> String {
+        let puzzle_label = match self.kind {
+            PuzzleKind::Conversion => format!(
+                "{}{}{}",
+                self.conversion.label(),
+                self.answer_mode.short_label(),
+                self.distractors_in_play().short_label()
+            ),
+            PuzzleKind::Bitwise | PuzzleKind::Arithmetic => self.kind.label().to_string(),
+            PuzzleKind::FixedPoint => q_format(self.bits.to_int(), self.fraction_bits),
+            // floats have a sign field of their own and BCD has no sign,
+            // so there is no number mode to show
+            PuzzleKind::HalfFloat | PuzzleKind::Bcd => {
+                return format!("{} {}", self.bits.label(), self.kind.label());
             },
         };
+        let number_label = self.number_mode.label_for(self.bits.to_int());
+        format!("{} {} {}", self.bits.label(), number_label, puzzle_label)
+    }
 
-        let current_number = current_number_signed.unsigned_abs();
+    /// Points for a correct answer before the streak bonus,
+    /// multiplied by the difficulty of the round in mixed games
+    pub fn base_points(&self) -> u32 {
+        let base_points = self.puzzle_base_points();
+        match self.game_mode {
+            GameMode::Mixed => base_points * mixed::difficulty(&self.bits, self.number_mode),
+            _ => base_points,
+        }
+    }
+
+    const fn puzzle_base_points(&self) -> u32 {
+        match self.kind {
+            PuzzleKind::Conversion => match self.answer_mode {
+                AnswerMode::MultipleChoice => self.distractors.base_points(),
+                AnswerMode::FreeEntry => self.answer_mode.base_points(),
+            },
+            // the other puzzles are always multiple choice
+            PuzzleKind::Bitwise
+            | PuzzleKind::HalfFloat
+            | PuzzleKind::FixedPoint
+            | PuzzleKind::Bcd => AnswerMode::MultipleChoice.base_points(),
+            // the result and the flags, two questions in one round
+            PuzzleKind::Arithmetic => 2 * AnswerMode::MultipleChoice.base_points(),
+        }
+    }
+
+    pub fn high_score_key(&self) -> String {
+        // one score for all widths and number modes of a mixed game
+        if self.game_mode == GameMode::Mixed {
+            return "mixed".to_string();
+        }
+        let lives_suffix = if self.game_mode.has_lives() {
+            self.lives.high_score_suffix()
+        } else {
+            ""
+        };
+        format!(
+            "{}{}{lives_suffix}",
+            self.puzzle_high_score_key(),
+            self.game_mode.high_score_suffix()
+        )
+    }
+
+    fn puzzle_high_score_key(&self) -> String {
+        let bits_key = self.bits.high_score_key();
+        let mode_suffix = self.number_mode.high_score_suffix();
+        match self.kind {
+            PuzzleKind::Conversion => format!(
+                "{}{}{}{}{}",
+                bits_key,
+                mode_suffix,
+                self.conversion.high_score_suffix(),
+                self.answer_mode.high_score_suffix(),
+                self.distractors_in_play().high_score_suffix()
+            ),
+            PuzzleKind::Bitwise => format!("{bits_key}{mode_suffix}-bitwise"),
+            PuzzleKind::Arithmetic => format!("{bits_key}{mode_suffix}-arith"),
+            PuzzleKind::HalfFloat => format!("{bits_key}-half"),
+            PuzzleKind::Bcd => format!("{bits_key}-bcd"),
+            PuzzleKind::FixedPoint => format!(
+                "{bits_key}{mode_suffix}-{}",
+                q_format(self.bits.to_int(), self.fraction_bits).to_lowercase()
+            ),
+        }
+    }
+}
 
-        // Calculate time based on difficulty
-        let time_total = 10.0 - (streak.min(8) as f64 * 0.5);
-        let time_left = time_total;
+pub struct BinaryNumbersPuzzle {
+    bits: Bits,
+    number_mode: NumberMode,
+    conversion: Conversion,
+    answer_mode: AnswerMode,
+    entry: String,   // typed answer in free entry mode
+    built_bits: u64, // bit pattern assembled in the bit builder
+    cursor: usize,   // bit builder cell under the cursor, 0 is the most significant bit
+    #[allow(dead_code)]
+    current_number: u64, // scaled value used for suggestions matching
+    raw_current_number: u64, // raw bit value (unscaled) for display
+    suggestions: Vec<i64>, // signed to support negative values, the first one is the answer
+    listed: Vec<i64>, // the suggestions in the order they are shown
+    selected_suggestion: Option<i64>,
+    round: Round,
+}
 
-        let selected_suggestion = Some(suggestions[0]);
-        let guess_result = None;
-        let last_points_awarded = 0;
+impl Puzzle for BinaryNumbersPuzzle {
+    fn new(config: &PuzzleConfig, streak: u32, rng: &mut impl Rng) -> Self {
+        let bits = config.bits.clone();
+        let number_mode = config.number_mode;
+
+        let scale = bits.scale_factor() as i64;
+        let num_bits = bits.to_int();
+        // a 1 bit sign-magnitude word is just +0 and -0
+        let count = usize::try_from(number_mode.distinct_values(num_bits))
+            .map_or(bits.suggestion_count(), |distinct| bits.suggestion_count().min(distinct));
+
+        // The first suggestion is the current number, more often one the player keeps missing
+        let raw = match config.target {
+            Some(raw) => raw,
+            None if config.is_adaptive() => config.weak_spots.draw(bits.mask(), rng),
+            None => rng.random_range(0..=bits.mask()),
+        };
+        let mut suggestions = vec![number_mode.value_of(raw, num_bits) * scale];
+
+        if config.distractors_in_play() == DistractorStrategy::Tricky {
+            // values out of range would give themselves away, unless they are read as decimals
+            let answer_is_decimal = config.conversion.answer_base() == Base::Decimal;
+            let fits = |value: i64| {
+                answer_is_decimal
+                    || number_mode.value_of(number_mode.raw_of(value, num_bits), num_bits) == value
+            };
+            for candidate in near_misses(raw, num_bits, number_mode, rng) {
+                if suggestions.len() < count
+                    && fits(candidate)
+                    && !suggestions.contains(&(candidate * scale))
+                {
+                    suggestions.push(candidate * scale);
+                }
+            }
+        }
+        while suggestions.len() < count {
+            let raw = rng.random_range(0..=bits.mask());
+            let num = number_mode.value_of(raw, num_bits) * scale;
+            if !suggestions.contains(&num) {
+                suggestions.push(num);
+            }
+        }
+
+        let current_number_signed = suggestions[0];
+
+        // Negative values are stored in the encoding of the number mode
+        let raw_current_number = bits.raw_bits(current_number_signed, number_mode);
+
+        let current_number = current_number_signed.unsigned_abs();
+
+        // The answer is listed anywhere among the suggestions
+        let mut listed = suggestions.clone();
+        listed.shuffle(rng);
+        let selected_suggestion = Some(listed[0]);
 
         Self {
             bits,
             number_mode,
+            conversion: config.conversion,
+            answer_mode: config.answer_mode,
+            entry: String::new(),
+            built_bits: 0,
+            cursor: 0,
             current_number,
             raw_current_number,
             suggestions,
-            time_total,
-            time_left,
+            listed,
             selected_suggestion,
-            guess_result,
-            last_points_awarded,
-            stats_snapshot: None,
-            skip_first_dt: true,
+            round: Round::new(streak),
+        }
+    }
+
+    fn round(&self) -> &Round {
+        &self.round
+    }
+
+    fn round_mut(&mut self) -> &mut Round {
+        &mut self.round
+    }
+
+    fn handle_answer_input(&mut self, input: KeyEvent) {
+        match self.answer_input() {
+            AnswerInput::Choice => self.handle_choice_input(input),
+            AnswerInput::Typed => self.handle_entry_input(input),
+            AnswerInput::BitBuilder => self.handle_bit_builder_input(input),
+        }
+    }
+
+    fn answer_height(&self, width: u16) -> u16 {
+        match self.answer_input() {
+            AnswerInput::Choice => self.choices().height(width),
+            AnswerInput::Typed => 3,
+            #[allow(clippy::cast_possible_truncation)]
+            AnswerInput::BitBuilder => {
+                self.bit_builder_lines(width.saturating_sub(2)).len() as u16 + 2
+            },
+        }
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        self.render_current_number(area, buf);
+    }
+
+    fn render_answer(&self, area: Rect, buf: &mut Buffer) {
+        match self.answer_input() {
+            AnswerInput::Choice => self.choices().render_ref(area, buf),
+            AnswerInput::Typed => self.render_entry(area, buf),
+            AnswerInput::BitBuilder => self.render_bit_builder(area, buf),
+        }
+    }
+
+    fn answer_hint(&self, width: u16) -> (&'static str, &'static str) {
+        let type_keys = match self.conversion.answer_base() {
+            Base::Decimal => "0-9",
+            Base::Binary | Base::Gray | Base::Permissions => "0 1",
+            Base::Hex => "0-9 A-F",
+            Base::Octal => "0-7",
+        };
+        match self.answer_input() {
+            AnswerInput::Choice => self.choices().hint(width),
+            AnswerInput::Typed => (type_keys, "type  "),
+            AnswerInput::BitBuilder => ("←→ Space 0 1", "edit  "),
+        }
+    }
+
+    fn attempt(&self) -> Option<Attempt> {
+        let result = self.round.guess_result?;
+        // compared as plain bits, one wrong bit would flip its neighbour in the Gray code
+        let to_raw = |pattern| match self.conversion.answer_base() {
+            Base::Gray => gray_decode(pattern),
+            _ => pattern,
+        };
+        let wrong_bits = match result {
+            GuessResult::Incorrect => self
+                .answered_pattern()
+                .map_or(0, |answered| to_raw(answered) ^ self.raw_current_number),
+            GuessResult::Correct | GuessResult::Timeout => 0,
+        };
+        Some(Attempt {
+            raw: self.raw_current_number,
+            wrong_bits: wrong_bits & self.bits.mask(),
+            correct: result == GuessResult::Correct,
+            slow: self.round.time_left < self.round.time_total / 2.0,
+        })
+    }
+
+    fn hints(&self) -> Vec<String> {
+        let num_bits = self.bits.to_int();
+        let mut values: Vec<String> = self.place_values().iter().map(i64::to_string).collect();
+        if self.number_mode == NumberMode::SignMagnitude {
+            values[0] = "±".to_string();
+        }
+        let mut place_values = values.join(" ");
+        // long words keep the highest and the lowest places to fit the panel, as long as
+        // there are enough places to leave some out
+        if place_values.chars().count() > 30 && values.len() > 5 {
+            place_values =
+                format!("{} {} … {}", values[0], values[1], values[values.len() - 4..].join(" "));
+        }
+        if self.number_mode == NumberMode::Excess {
+            let bias = NumberMode::bias(num_bits) * self.bits.scale_factor() as i64;
+            place_values.push_str(&format!(" -{bias}"));
+        }
+
+        let mut hints = vec![place_values];
+        let top = num_bits / 2;
+        if top > 0 {
+            hints.push(format!("top {top} bits make {}", self.partial_sum(top)));
+        }
+        hints
+    }
+}
+
+impl BinaryNumbersPuzzle {
+    fn handle_choice_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_right(x) || keybinds::is_down(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.listed, self.selected_suggestion, true);
+            },
+            x if keybinds::is_left(x) || keybinds::is_up(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.listed, self.selected_suggestion, false);
+            },
+            x if keybinds::is_select(x) => {
+                if let Some(selected) = self.selected_suggestion {
+                    self.round.resolve(self.is_correct_guess(selected));
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn handle_entry_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_select(x) && !self.entry.is_empty() => {
+                self.round.resolve(self.entry_is_correct());
+            },
+            x if keybinds::is_delete(x) => {
+                self.entry.pop();
+            },
+            KeyEvent { code: KeyCode::Char(c), .. } => self.push_entry_char(c),
+            _ => {},
+        }
+    }
+
+    fn handle_bit_builder_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_left(x) => self.move_cursor(-1),
+            x if keybinds::is_right(x) => self.move_cursor(1),
+            x if keybinds::is_select(x) => {
+                self.round
+                    .resolve(self.built_bits == self.pattern_in(self.conversion.answer_base()));
+            },
+            KeyEvent { code: KeyCode::Char(' '), .. } => self.toggle_bit_at_cursor(),
+            KeyEvent { code: KeyCode::Char(c @ ('0' | '1')), .. } => {
+                self.set_bit_at_cursor(c == '1');
+                self.move_cursor(1);
+            },
+            _ => {},
+        }
+    }
+
+    /// What each bit of the word adds to its value, from the most significant bit down,
+    /// scaled like the answers. The sign bit of sign-magnitude only flips the sign.
+    fn place_values(&self) -> Vec<i64> {
+        let num_bits = self.bits.to_int();
+        let scale = self.bits.scale_factor() as i64;
+        let sign_bit = 1i64 << (num_bits - 1);
+        let top = match self.number_mode {
+            NumberMode::Unsigned | NumberMode::Excess => sign_bit,
+            NumberMode::Signed => -sign_bit,
+            NumberMode::OnesComplement => -(sign_bit - 1),
+            NumberMode::SignMagnitude => 0,
+        };
+        (0..num_bits)
+            .rev()
+            .map(|bit| if bit == num_bits - 1 { top } else { 1 << bit } * scale)
+            .collect()
+    }
+
+    /// Value of the highest `top` bits of the current number on their own,
+    /// with the bias already taken off for excess-K
+    fn partial_sum(&self, top: u32) -> i64 {
+        let num_bits = self.bits.to_int();
+        let sum: i64 = self
+            .place_values()
+            .iter()
+            .take(top as usize)
+            .enumerate()
+            .filter(|&(i, _)| self.raw_current_number >> (num_bits - 1 - i as u32) & 1 == 1)
+            .map(|(_, value)| value)
+            .sum();
+        let negative = self.raw_current_number >> (num_bits - 1) == 1;
+        match self.number_mode {
+            NumberMode::SignMagnitude if negative => -sum,
+            NumberMode::Excess => {
+                sum - NumberMode::bias(num_bits) * self.bits.scale_factor() as i64
+            },
+            _ => sum,
+        }
+    }
+
+    pub fn is_correct_guess(&self, guess: i64) -> bool {
+        guess == self.suggestions[0]
+    }
+
+    /// The current number in the binary notation of the prompt, plain or Gray code
+    pub fn current_to_binary_string(&self) -> String {
+        self.binary_string(self.pattern_in(self.conversion.prompt_base()))
+    }
+
+    fn binary_string(&self, raw_bits: u64) -> String {
+        grouped_binary(raw_bits, self.bits.to_int(), self.group_size())
+    }
+
+    /// Bits per group in binary strings: nibbles, or triplets when octal digits are involved
+    fn group_size(&self) -> usize {
+        if self.conversion.involves_octal() { 3 } else { 4 }
+    }
+
+    /// Free entry of a binary answer means building the pattern bit by bit
+    fn answer_input(&self) -> AnswerInput {
+        match (self.answer_mode, self.conversion.answer_base()) {
+            (AnswerMode::MultipleChoice, _) => AnswerInput::Choice,
+            (AnswerMode::FreeEntry, Base::Binary | Base::Gray | Base::Permissions) => {
+                AnswerInput::BitBuilder
+            },
+            (AnswerMode::FreeEntry, _) => AnswerInput::Typed,
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = self.bits.to_int() as usize - 1;
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    fn cursor_mask(&self) -> u64 {
+        1 << (self.bits.to_int() as usize - 1 - self.cursor)
+    }
+
+    pub fn toggle_bit_at_cursor(&mut self) {
+        self.built_bits ^= self.cursor_mask();
+    }
+
+    pub fn set_bit_at_cursor(&mut self, set: bool) {
+        if set {
+            self.built_bits |= self.cursor_mask();
+        } else {
+            self.built_bits &= !self.cursor_mask();
+        }
+    }
+
+    /// Append a typed character if it is valid for the answer notation
+    pub fn push_entry_char(&mut self, c: char) {
+        let allowed = match self.conversion.answer_base() {
+            Base::Decimal => {
+                c.is_ascii_digit()
+                    || (c == '-' && self.entry.is_empty() && self.number_mode.is_signed())
+            },
+            Base::Binary | Base::Gray => c == '0' || c == '1',
+            Base::Hex => c.is_ascii_hexdigit(),
+            Base::Octal => ('0'..='7').contains(&c),
+            // permissions are entered with the bit builder
+            Base::Permissions => false,
+        };
+        if allowed && self.entry.len() < self.max_entry_len() {
+            self.entry.push(c.to_ascii_uppercase());
+        }
+    }
+
+    fn max_entry_len(&self) -> usize {
+        match self.conversion.answer_base() {
+            // sign plus the digits of the largest magnitude
+            Base::Decimal => 1 + self.bits.upper_bound().to_string().len(),
+            Base::Binary | Base::Gray | Base::Permissions => self.bits.to_int() as usize,
+            Base::Hex => self.bits.hex_digits(),
+            Base::Octal => self.bits.octal_digits(),
         }
     }
 
-    pub fn suggestions(&self) -> &[i32] {
-        &self.suggestions
+    pub fn entry_is_correct(&self) -> bool {
+        match self.conversion.answer_base() {
+            Base::Decimal => self.entry.parse::<i64>().is_ok_and(|v| self.is_correct_guess(v)),
+            base @ (Base::Binary | Base::Gray) => u64::from_str_radix(&self.entry, 2)
+                .is_ok_and(|pattern| pattern == self.pattern_in(base)),
+            Base::Hex => u64::from_str_radix(&self.entry, 16)
+                .is_ok_and(|hex| hex == self.raw_current_number << self.bits.scale_shift()),
+            Base::Octal => u64::from_str_radix(&self.entry, 8)
+                .is_ok_and(|oct| oct == self.raw_current_number << self.bits.scale_shift()),
+            Base::Permissions => false,
+        }
     }
 
-    pub fn is_correct_guess(&self, guess: i32) -> bool {
-        guess == self.suggestions[0]
+    /// Format a suggestion in the notation the answers are given in
+    fn format_answer(&self, value: i64) -> String {
+        self.format_value(value, self.conversion.answer_base())
     }
 
-    pub fn current_to_binary_string(&self) -> String {
-        let width = self.bits.to_int() as usize;
-        let raw = format!("{:0width$b}", self.raw_current_number, width = width);
-        raw.chars()
-            .collect::<Vec<_>>()
-            .chunks(4)
-            .map(|chunk| chunk.iter().collect::<String>())
-            .collect::<Vec<_>>()
-            .join(" ")
-    }
-
-    pub fn run(&mut self, dt: f64) {
-        if self.skip_first_dt {
-            self.skip_first_dt = false;
-            return;
+    fn format_value(&self, value: i64, base: Base) -> String {
+        match base {
+            Base::Decimal => value.to_string(),
+            Base::Binary => {
+                let binary = self.binary_string(self.bits.raw_bits(value, self.number_mode));
+                format!("{binary}{}", self.bits.scale_suffix().unwrap_or_default())
+            },
+            // the scaled pattern, so a shift shows up as trailing zero digits
+            Base::Hex => format!(
+                "0x{:0width$X}",
+                self.bits.raw_bits(value, self.number_mode) << self.bits.scale_shift(),
+                width = self.bits.hex_digits()
+            ),
+            // C style leading zero, like chmod 0750
+            Base::Octal => format!(
+                "0{:0width$o}",
+                self.bits.raw_bits(value, self.number_mode) << self.bits.scale_shift(),
+                width = self.bits.octal_digits()
+            ),
+            Base::Permissions => {
+                permission_string(self.bits.raw_bits(value, self.number_mode), self.bits.to_int())
+            },
+            Base::Gray => {
+                let gray = gray_encode(self.bits.raw_bits(value, self.number_mode));
+                format!(
+                    "{}{}",
+                    self.binary_string(gray),
+                    self.bits.scale_suffix().unwrap_or_default()
+                )
+            },
         }
-        if self.guess_result.is_some() {
-            return;
+    }
+
+    /// The answer given, as a pattern in the notation of the answers like `pattern_in`
+    fn answered_pattern(&self) -> Option<u64> {
+        let base = self.conversion.answer_base();
+        let shift = self.bits.scale_shift();
+        match self.answer_input() {
+            AnswerInput::Choice => self.selected_suggestion.map(|value| {
+                let raw = self.bits.raw_bits(value, self.number_mode);
+                if base == Base::Gray { gray_encode(raw) } else { raw }
+            }),
+            AnswerInput::BitBuilder => Some(self.built_bits),
+            AnswerInput::Typed => match base {
+                Base::Decimal => {
+                    self.entry.parse().ok().map(|value| self.bits.raw_bits(value, self.number_mode))
+                },
+                Base::Binary | Base::Gray => u64::from_str_radix(&self.entry, 2).ok(),
+                Base::Hex => u64::from_str_radix(&self.entry, 16).ok().map(|hex| hex >> shift),
+                Base::Octal => u64::from_str_radix(&self.entry, 8).ok().map(|oct| oct >> shift),
+                Base::Permissions => None,
+            },
         }
-        self.time_left -= dt;
-        if self.time_left <= 0.0 {
-            self.guess_result = Some(GuessResult::Timeout);
+    }
+
+    /// The bits of the current number as written in a binary notation
+    fn pattern_in(&self, base: Base) -> u64 {
+        match base {
+            Base::Gray => gray_encode(self.raw_current_number),
+            _ => self.raw_current_number,
         }
     }
 }
 
-impl Widget for &mut BinaryNumbersGame {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        self.render_ref(area, buf);
+/// Bit pattern of the given width, grouped from the least significant end,
+/// so 8 bits in triplets read 10 101 011
+pub fn grouped_binary(raw_bits: u64, num_bits: u32, group: usize) -> String {
+    let width = num_bits as usize;
+    let raw = format!("{raw_bits:0width$b}");
+    let mut grouped = String::new();
+    for (i, c) in raw.chars().enumerate() {
+        if i > 0 && (width - i).is_multiple_of(group) {
+            grouped.push(' ');
+        }
+        grouped.push(c);
     }
+    grouped
 }
 
 // Simple ASCII gauge renderer to avoid variable glyph heights from Unicode block elements
@@ -896,25 +2314,34 @@ fn render_ascii_gauge(area: Rect, buf: &mut Buffer, ratio: f64, color: Color) {
 
 struct HighScores {
     scores: HashMap<String, u32>,
+    /// Daily challenge scores by date, kept in a section of their own
+    daily: BTreeMap<String, u32>,
 }
 
 impl HighScores {
-    const FILE: &'static str = "binbreak_highscores.txt";
+    const DAILY_SECTION: &'static str = "[daily]";
 
     fn empty() -> Self {
-        Self { scores: HashMap::new() }
+        Self { scores: HashMap::new(), daily: BTreeMap::new() }
     }
 
     fn load() -> Self {
         let mut hs = Self::empty();
-        if let Ok(mut file) = File::open(Self::FILE) {
+        if let Ok(mut file) = File::open(storage::high_scores()) {
             let mut contents = String::new();
             if file.read_to_string(&mut contents).is_ok() {
+                let mut in_daily_section = false;
                 for line in contents.lines() {
-                    if let Some((k, v)) = line.split_once('=')
+                    if line.trim() == Self::DAILY_SECTION {
+                        in_daily_section = true;
+                    } else if let Some((k, v)) = line.split_once('=')
                         && let Ok(score) = v.trim().parse::<u32>()
                     {
-                        hs.scores.insert(k.trim().to_string(), score);
+                        if in_daily_section {
+                            hs.daily.insert(k.trim().to_string(), score);
+                        } else {
+                            hs.scores.insert(k.trim().to_string(), score);
+                        }
                     }
                 }
             }
@@ -923,16 +2350,34 @@ impl HighScores {
     }
 
     fn save(&self) -> std::io::Result<()> {
+        // the classic drill of every preset width comes first, even without a score yet
+        let classic_keys: Vec<String> = Bits::PRESETS
+            .iter()
+            .flat_map(|bits| {
+                [NumberMode::Unsigned, NumberMode::Signed]
+                    .map(|mode| PuzzleConfig::new(bits.clone(), mode).high_score_key())
+            })
+            .collect();
         let mut data = String::new();
-        for key in [
-            "4u", "4s", "44u", "44s", "48u", "48s", "412u", "412s", "8u", "8s", "12u", "12s",
-            "16u", "16s",
-        ] {
+        for key in &classic_keys {
+            let val = self.get(key);
+            let _ = writeln!(data, "{key}={val}");
+        }
+        // scores of the other drills follow in a stable order
+        let mut other_keys: Vec<&String> =
+            self.scores.keys().filter(|k| !classic_keys.contains(k)).collect();
+        other_keys.sort();
+        for key in other_keys {
             let val = self.get(key);
             let _ = writeln!(data, "{key}={val}");
         }
-        let mut file = File::create(Self::FILE)?;
-        file.write_all(data.as_bytes())
+        if !self.daily.is_empty() {
+            let _ = writeln!(data, "{}", Self::DAILY_SECTION);
+            for (date, score) in &self.daily {
+                let _ = writeln!(data, "{date}={score}");
+            }
+        }
+        storage::write(&storage::high_scores(), &data)
     }
 
     fn get(&self, bits: &str) -> u32 {
@@ -942,6 +2387,32 @@ impl HighScores {
     fn update(&mut self, bits: &str, score: u32) {
         self.scores.insert(bits.to_string(), score);
     }
+
+    fn record_daily(&mut self, date: &str, score: u32) {
+        self.daily.insert(date.to_string(), score);
+    }
+
+    /// The high score of the game, or the score of the day for a daily challenge
+    fn recorded_score(&self, config: &PuzzleConfig) -> u32 {
+        match config.game_mode {
+            GameMode::Classic | GameMode::TimeAttack | GameMode::Mixed => {
+                self.get(&config.high_score_key())
+            },
+            GameMode::Daily { day } => {
+                self.daily.get(&daily::date_of_day(day)).copied().unwrap_or(0)
+            },
+            GameMode::Zen | GameMode::Study => 0,
+        }
+    }
+
+    /// Only the first attempt of a daily challenge counts, and practice never does
+    fn is_scored(&self, config: &PuzzleConfig) -> bool {
+        match config.game_mode {
+            GameMode::Classic | GameMode::TimeAttack | GameMode::Mixed => true,
+            GameMode::Zen | GameMode::Study => false,
+            GameMode::Daily { day } => !self.daily.contains_key(&daily::date_of_day(day)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -949,24 +2420,19 @@ mod tests {
     use super::*;
     use crossterm::event::{KeyEventKind, KeyEventState, KeyModifiers};
     use std::fs;
-    use std::sync::Mutex;
-
-    static HS_LOCK: Mutex<()> = Mutex::new(());
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
 
+    /// Games save high scores, weak spots and study cards as they go, each test into a
+    /// directory of its own
     fn with_high_score_file<F: FnOnce()>(f: F) {
-        #[allow(clippy::expect_used)]
-        let _guard = HS_LOCK.lock().expect("Failed to lock high score mutex");
-        let original = fs::read_to_string(HighScores::FILE).ok();
-        f();
-        // restore
-        match original {
-            Some(data) => {
-                let _ = fs::write(HighScores::FILE, data);
-            },
-            None => {
-                let _ = fs::remove_file(HighScores::FILE);
-            },
-        }
+        storage::with_files_in_temp_dir(|_| f());
     }
 
     #[test]
@@ -980,38 +2446,100 @@ mod tests {
         assert_eq!(Bits::FourShift4.suggestion_count(), 3);
 
         assert_eq!(Bits::FourShift8.scale_factor(), 256);
-        assert_eq!(Bits::FourShift12.high_score_key(), 412);
+        assert_eq!(Bits::FourShift12.high_score_key(), "412");
         assert_eq!(Bits::Eight.upper_bound(), 255);
 
         assert_eq!(Bits::Sixteen.suggestion_count(), 6);
     }
 
+    #[test]
+    fn high_score_keys_are_derived_from_width_and_shift() {
+        let keys: Vec<String> = Bits::PRESETS.iter().map(Bits::high_score_key).collect();
+        assert_eq!(keys, ["4", "44", "48", "412", "8", "9", "12", "16", "24", "32"]);
+
+        let custom = Bits::Custom(CustomBits { width: 1, shift: 2, suggestions: 2 });
+        assert_eq!(custom.label(), "1 bit*4");
+        assert_eq!(custom.scale_suffix().as_deref(), Some(" x4"));
+        let config = PuzzleConfig::new(custom, NumberMode::Signed);
+        assert_eq!(config.high_score_key(), "custom1.2.2s");
+    }
+
+    #[test]
+    fn bit_puzzles_share_the_unsigned_score_of_modes_they_cant_tell_apart() {
+        for kind in [PuzzleKind::Bitwise, PuzzleKind::Arithmetic] {
+            let key =
+                |mode| PuzzleConfig::new(Bits::Sixteen, mode).with_kind(kind).high_score_key();
+            for mode in [NumberMode::SignMagnitude, NumberMode::OnesComplement, NumberMode::Excess]
+            {
+                assert_eq!(key(mode), key(NumberMode::Unsigned));
+            }
+            assert_ne!(key(NumberMode::Signed), key(NumberMode::Unsigned));
+        }
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Excess).with_kind(PuzzleKind::Bcd);
+        assert_eq!(config.number_mode, NumberMode::Excess);
+    }
+
+    #[test]
+    fn custom_bits_steppers_keep_the_combination_valid() {
+        let custom = CustomBits { width: 28, shift: 4, suggestions: 6 };
+        assert_eq!(custom.step_shift(true).shift, 0); // the scaled pattern is already 32 bits
+        let wider = custom.step_width(true);
+        assert_eq!((wider.width, wider.shift), (29, 3));
+        let widest = wider.step_width(true).step_width(true).step_width(true);
+        assert_eq!((widest.width, widest.shift), (32, 0));
+        assert_eq!(widest.step_width(true).width, 1);
+
+        let narrow = CustomBits { width: 2, shift: 0, suggestions: 4 }.step_width(false);
+        assert_eq!((narrow.width, narrow.suggestions), (1, 2));
+        assert_eq!(narrow.step_suggestions(true).suggestions, 2);
+        assert_eq!(narrow.step_width(false).width, 32);
+
+        for kind in [PuzzleKind::Conversion, PuzzleKind::Bitwise, PuzzleKind::Arithmetic] {
+            for mode in [NumberMode::Unsigned, NumberMode::Signed] {
+                let config = PuzzleConfig::new(Bits::Custom(narrow), mode).with_kind(kind);
+                let _ = new_game(config);
+            }
+        }
+    }
+
     #[test]
     fn puzzle_generation_unique_and_scaled() {
-        let p = BinaryNumbersPuzzle::new(Bits::FourShift4.clone(), NumberMode::Unsigned, 0);
+        let p = BinaryNumbersPuzzle::new(
+            &PuzzleConfig::new(Bits::FourShift4, NumberMode::Unsigned),
+            0,
+            &mut rand::rng(),
+        );
         let scale = Bits::FourShift4.scale_factor();
-        assert_eq!(p.suggestions().len(), Bits::FourShift4.suggestion_count());
+        assert_eq!(p.suggestions.len(), Bits::FourShift4.suggestion_count());
         // uniqueness
-        let mut sorted = p.suggestions().to_vec();
+        let mut sorted = p.suggestions.to_vec();
         sorted.sort_unstable();
         for pair in sorted.windows(2) {
             assert_ne!(pair[0], pair[1]);
         }
         // scaling property
-        for &s in p.suggestions() {
+        for &s in &p.suggestions {
             assert_eq!(s.unsigned_abs() % scale, 0);
         }
         // current number must be one of suggestions and raw_current_number * scale == current_number
-        assert!(p.suggestions().contains(&(p.current_number as i32)));
+        assert!(p.suggestions.contains(&(p.current_number as i64)));
         assert_eq!(p.raw_current_number * scale, p.current_number);
     }
 
     #[test]
     fn binary_string_formatting_groups_every_four_bits() {
-        let mut p = BinaryNumbersPuzzle::new(Bits::Eight, NumberMode::Unsigned, 0);
+        let mut p = BinaryNumbersPuzzle::new(
+            &PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned),
+            0,
+            &mut rand::rng(),
+        );
         p.raw_current_number = 0xAB; // 171 = 10101011
         assert_eq!(p.current_to_binary_string(), "1010 1011");
-        let mut p4 = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Unsigned, 0);
+        let mut p4 = BinaryNumbersPuzzle::new(
+            &PuzzleConfig::new(Bits::Four, NumberMode::Unsigned),
+            0,
+            &mut rand::rng(),
+        );
         p4.raw_current_number = 0b0101;
         assert_eq!(p4.current_to_binary_string(), "0101");
     }
@@ -1019,7 +2547,11 @@ mod tests {
     #[test]
     fn signed_mode_negative_numbers_show_sign_bit() {
         // Test 4-bit signed mode with a negative number
-        let mut p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Signed, 0);
+        let mut p = BinaryNumbersPuzzle::new(
+            &PuzzleConfig::new(Bits::Four, NumberMode::Signed),
+            0,
+            &mut rand::rng(),
+        );
         // In 4-bit two's complement, -8 is represented as 1000
         p.raw_current_number = 0b1000; // -8 in 4-bit two's complement
         assert_eq!(p.current_to_binary_string(), "1000", "4-bit: -8 should be 1000");
@@ -1029,7 +2561,11 @@ mod tests {
         assert_eq!(p.current_to_binary_string(), "1111", "4-bit: -1 should be 1111");
 
         // Test 8-bit signed mode with a negative number
-        let mut p8 = BinaryNumbersPuzzle::new(Bits::Eight, NumberMode::Signed, 0);
+        let mut p8 = BinaryNumbersPuzzle::new(
+            &PuzzleConfig::new(Bits::Eight, NumberMode::Signed),
+            0,
+            &mut rand::rng(),
+        );
         // In 8-bit two's complement, -128 is represented as 10000000
         p8.raw_current_number = 0b10000000; // -128 in 8-bit two's complement
         assert_eq!(p8.current_to_binary_string(), "1000 0000", "8-bit: -128 should be 1000 0000");
@@ -1039,12 +2575,55 @@ mod tests {
         assert_eq!(p8.current_to_binary_string(), "1111 1111", "8-bit: -1 should be 1111 1111");
     }
 
+    #[test]
+    fn alternate_signed_encodings_decode_and_encode() {
+        let decode = |mode: NumberMode| mode.value_of(0b1011, 4);
+        assert_eq!(decode(NumberMode::Signed), -5);
+        assert_eq!(decode(NumberMode::SignMagnitude), -3);
+        assert_eq!(decode(NumberMode::OnesComplement), -4);
+        assert_eq!(decode(NumberMode::Excess), 4); // excess-7
+        assert_eq!(NumberMode::Excess.label_for(8), "EXCESS-127");
+
+        // negative zero decodes to 0, which is encoded as all zeros
+        assert_eq!(NumberMode::SignMagnitude.value_of(0b1000, 4), 0);
+        assert_eq!(NumberMode::OnesComplement.value_of(0b1111, 4), 0);
+        for mode in NumberMode::ALL {
+            for raw in 0..16 {
+                let value = mode.value_of(raw, 4);
+                if value != 0 {
+                    assert_eq!(mode.raw_of(value, 4), raw, "{mode:?} {raw:04b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn each_number_mode_has_its_own_puzzles_and_high_score_key() {
+        for mode in NumberMode::ALL {
+            let config = PuzzleConfig::new(Bits::FourShift4, mode)
+                .with_conversion(Conversion::DecimalToBinary);
+            assert_eq!(config.high_score_key(), format!("44{}-d2b", mode.high_score_suffix()));
+            let p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+            assert_eq!(mode.value_of(p.raw_current_number, 4) * 16, p.suggestions[0]);
+        }
+        // only +0 and -0, so there is a single suggestion
+        let single = PuzzleConfig::new(
+            Bits::Custom(CustomBits { width: 1, shift: 0, suggestions: 2 }),
+            NumberMode::SignMagnitude,
+        );
+        assert_eq!(BinaryNumbersPuzzle::new(&single, 0, &mut rand::rng()).suggestions, [0]);
+    }
+
     #[test]
     fn signed_mode_puzzle_generates_correct_raw_bits_for_negative() {
         // Generate many puzzles and check that when we have a negative number,
         // the raw_current_number has the sign bit set correctly
         for _ in 0..20 {
-            let p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Signed, 0);
+            let p = BinaryNumbersPuzzle::new(
+                &PuzzleConfig::new(Bits::Four, NumberMode::Signed),
+                0,
+                &mut rand::rng(),
+            );
             let current_signed = p.suggestions[0];
 
             if current_signed < 0 {
@@ -1071,30 +2650,332 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reverse_mode_formats_answers_as_grouped_binary() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Signed)
+            .with_conversion(Conversion::DecimalToBinary);
+        let p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        assert_eq!(p.format_answer(-1), "1111 1111");
+        assert_eq!(p.format_answer(-128), "1000 0000");
+        assert_eq!(p.format_answer(5), "0000 0101");
+
+        let config = PuzzleConfig::new(Bits::FourShift4, NumberMode::Unsigned)
+            .with_conversion(Conversion::DecimalToBinary);
+        let p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        assert_eq!(p.format_answer(160), "1010 x16");
+    }
+
+    #[test]
+    fn reverse_mode_has_its_own_high_score_key() {
+        let classic = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned);
+        assert_eq!(classic.high_score_key(), "8u");
+        let reverse = classic.with_conversion(Conversion::DecimalToBinary);
+        assert_eq!(reverse.high_score_key(), "8u-d2b");
+    }
+
+    #[test]
+    fn free_entry_accepts_only_valid_characters_and_checks_target() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Signed)
+            .with_answer_mode(AnswerMode::FreeEntry);
+        let mut p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        for c in "1-x2".chars() {
+            p.push_entry_char(c);
+        }
+        assert_eq!(p.entry, "12", "minus sign is only allowed first, letters never");
+
+        p.entry.clear();
+        for c in p.suggestions[0].to_string().chars() {
+            p.push_entry_char(c);
+        }
+        assert!(p.entry_is_correct());
+        p.entry.push('0');
+        assert!(!p.entry_is_correct());
+
+        let unsigned = PuzzleConfig::new(Bits::Four, NumberMode::Unsigned)
+            .with_answer_mode(AnswerMode::FreeEntry);
+        let mut p = BinaryNumbersPuzzle::new(&unsigned, 0, &mut rand::rng());
+        p.push_entry_char('-');
+        assert!(p.entry.is_empty(), "no minus sign in unsigned mode");
+    }
+
+    #[test]
+    fn free_entry_scores_double_and_has_its_own_high_score_key() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Four, NumberMode::Unsigned)
+                .with_answer_mode(AnswerMode::FreeEntry);
+            assert_eq!(config.high_score_key(), "4u-typed");
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config);
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!(g.score, 20);
+        });
+    }
+
+    #[test]
+    fn tricky_distractors_score_more_and_have_their_own_high_score_key() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned)
+                .with_distractors(DistractorStrategy::Tricky);
+            assert_eq!(config.high_score_key(), "8u-tricky");
+            assert_eq!(config.mode_label(), "8 bit UNSIGNED BIN → DEC TRICKY");
+            // typed answers have no distractors to be tricky with
+            let typed = config.clone().with_answer_mode(AnswerMode::FreeEntry);
+            assert_eq!(typed.high_score_key(), "8u-typed");
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config);
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!(g.score, 15);
+        });
+    }
+
+    #[test]
+    fn near_misses_come_from_typical_slips() {
+        let mut rng = rand::rng();
+        let misses = near_misses(0b0001_0110, 8, NumberMode::Unsigned, &mut rng);
+        // bit reversed, nibble swapped, weighted one power up and down
+        for expected in [0b0110_1000, 0b0110_0001, 0b0010_1100, 0b0000_1011] {
+            assert!(misses.contains(&expected), "{expected} missing from {misses:?}");
+        }
+
+        let misses = near_misses(0b1000_0011, 8, NumberMode::Signed, &mut rng);
+        // -125 with the sign dropped, read unsigned, as sign-magnitude and as ones' complement
+        for expected in [125, 131, -3, -124] {
+            assert!(misses.contains(&expected), "{expected} missing from {misses:?}");
+        }
+    }
+
+    #[test]
+    fn tricky_suggestions_stay_unique_and_in_range() {
+        let config = PuzzleConfig::new(Bits::Sixteen, NumberMode::Signed)
+            .with_conversion(Conversion::DecimalToBinary)
+            .with_distractors(DistractorStrategy::Tricky);
+        for _ in 0..50 {
+            let p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+            assert_eq!(p.suggestions.len(), Bits::Sixteen.suggestion_count());
+            let mut sorted = p.suggestions.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            assert_eq!(sorted.len(), p.suggestions.len());
+            for &s in &p.suggestions {
+                assert!((i64::from(i16::MIN)..=i64::from(i16::MAX)).contains(&s));
+            }
+        }
+    }
+
+    #[test]
+    fn answer_is_not_always_listed_first() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned);
+        let positions: Vec<usize> = (0..50)
+            .map(|_| BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng()).choices().correct)
+            .collect();
+        assert!(positions.iter().any(|&position| position != positions[0]));
+    }
+
+    #[test]
+    fn bit_builder_cursor_flips_bits_from_the_most_significant_end() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned)
+            .with_conversion(Conversion::DecimalToBinary)
+            .with_answer_mode(AnswerMode::FreeEntry);
+        let mut p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        assert_eq!(p.answer_input(), AnswerInput::BitBuilder);
+        p.toggle_bit_at_cursor();
+        assert_eq!(p.built_bits, 0b1000_0000);
+        p.move_cursor(-1);
+        assert_eq!(p.cursor, 0, "cursor stays on the first cell");
+        p.move_cursor(20);
+        assert_eq!(p.cursor, 7, "cursor stays on the last cell");
+        p.set_bit_at_cursor(true);
+        assert_eq!(p.built_bits, 0b1000_0001);
+        p.set_bit_at_cursor(false);
+        assert_eq!(p.built_bits, 0b1000_0000);
+    }
+
+    #[test]
+    fn bit_builder_accepts_twos_complement_pattern_for_signed_target() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Four, NumberMode::Signed)
+                .with_conversion(Conversion::DecimalToBinary)
+                .with_answer_mode(AnswerMode::FreeEntry);
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config);
+            let target = g.puzzle.raw_current_number;
+            for bit in (0..4).rev() {
+                let c = if target & (1 << bit) == 0 { '0' } else { '1' };
+                g.handle_game_input(key(KeyCode::Char(c)));
+            }
+            g.handle_game_input(key(KeyCode::Enter));
+            assert_eq!(g.puzzle.round.guess_result, Some(GuessResult::Correct));
+            assert_eq!(
+                g.puzzle.built_bits,
+                Bits::Four.raw_bits(g.puzzle.suggestions[0], NumberMode::Signed)
+            );
+        });
+    }
+
+    #[test]
+    fn hex_shows_scaled_pattern_and_twos_complement() {
+        let hex = |bits: Bits, mode: NumberMode, value: i64| {
+            let config = PuzzleConfig::new(bits, mode).with_conversion(Conversion::BinaryToHex);
+            BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng()).format_answer(value)
+        };
+        assert_eq!(hex(Bits::Four, NumberMode::Unsigned, 10), "0xA");
+        assert_eq!(hex(Bits::FourShift4, NumberMode::Unsigned, 160), "0xA0");
+        assert_eq!(hex(Bits::FourShift12, NumberMode::Unsigned, 3 * 4096), "0x3000");
+        assert_eq!(hex(Bits::FourShift8, NumberMode::Signed, -256), "0xF00");
+        assert_eq!(hex(Bits::Eight, NumberMode::Signed, -1), "0xFF");
+        assert_eq!(hex(Bits::Twelve, NumberMode::Unsigned, 255), "0x0FF");
+        assert_eq!(hex(Bits::Sixteen, NumberMode::Signed, -32768), "0x8000");
+    }
+
+    #[test]
+    fn hex_free_entry_is_case_insensitive() {
+        let config = PuzzleConfig::new(Bits::FourShift4, NumberMode::Unsigned)
+            .with_conversion(Conversion::BinaryToHex)
+            .with_answer_mode(AnswerMode::FreeEntry);
+        let mut p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        let answer = p.format_answer(p.suggestions[0]).trim_start_matches("0x").to_lowercase();
+        for c in answer.chars().chain("g".chars()) {
+            p.push_entry_char(c);
+        }
+        assert_eq!(p.entry, answer.to_uppercase());
+        assert!(p.entry_is_correct());
+    }
+
+    #[test]
+    fn each_conversion_has_its_own_high_score_key() {
+        let keys: Vec<String> = Conversion::ALL
+            .iter()
+            .map(|c| PuzzleConfig::new(Bits::Eight, NumberMode::Signed).with_conversion(*c))
+            .map(|config| config.high_score_key())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "8s", "8s-d2b", "8s-b2h", "8s-h2b", "8s-h2d", "8s-b2o", "8s-o2b", "8s-g2b",
+                "8s-b2g", "8s-g2d", "8s-d2g", "8s-p2o", "8s-o2p"
+            ]
+        );
+    }
+
+    #[test]
+    fn gray_drills_map_the_number_through_the_gray_code() {
+        // neighbours differ in exactly one bit, also when wrapping around
+        for raw in 0..16u64 {
+            assert_eq!((gray_encode(raw) ^ gray_encode((raw + 1) % 16)).count_ones(), 1);
+        }
+
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned)
+            .with_conversion(Conversion::GrayToDecimal);
+        let mut p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        p.raw_current_number = 0b0110_1011;
+        assert_eq!(p.current_to_binary_string(), "0101 1110");
+
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned)
+            .with_conversion(Conversion::BinaryToGray)
+            .with_answer_mode(AnswerMode::FreeEntry);
+        let mut p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        assert_eq!(p.format_answer(0b0110_1011), "0101 1110");
+        p.raw_current_number = 0b0110_1011;
+        for c in "01011110".chars() {
+            p.set_bit_at_cursor(c == '1');
+            p.move_cursor(1);
+        }
+        p.handle_answer_input(key(KeyCode::Enter));
+        assert_eq!(p.round.guess_result, Some(GuessResult::Correct));
+    }
+
+    #[test]
+    fn octal_drill_groups_binary_in_triplets() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned)
+            .with_conversion(Conversion::BinaryToOctal);
+        let mut p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        p.raw_current_number = 0xAB;
+        assert_eq!(p.current_to_binary_string(), "10 101 011");
+        assert_eq!(p.format_answer(0xAB), "0253");
+
+        let config = PuzzleConfig::new(Bits::FourShift4, NumberMode::Unsigned)
+            .with_conversion(Conversion::BinaryToOctal);
+        let p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        assert_eq!(p.format_answer(0xF0), "0360");
+    }
+
+    #[test]
+    fn permission_drills_render_rwx_triplets() {
+        assert_eq!(permission_string(0o750, 9), "rwxr-x---");
+        assert_eq!(permission_string(0o644, 9), "rw-r--r--");
+
+        let config = PuzzleConfig::new(Bits::Nine, NumberMode::Unsigned)
+            .with_conversion(Conversion::PermissionsToOctal);
+        let p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        assert_eq!(p.format_answer(0o755), "0755");
+        assert_eq!(p.format_value(0o755, Base::Permissions), "rwxr-xr-x");
+
+        assert!(Conversion::PermissionsToOctal.is_available_for(&Bits::Nine));
+        assert!(!Conversion::OctalToPermissions.is_available_for(&Bits::Eight));
+        assert!(Conversion::BinaryToOctal.is_available_for(&Bits::Eight));
+    }
+
+    #[test]
+    fn thirty_two_bit_words_keep_their_full_range() {
+        assert_eq!(Bits::ThirtyTwo.upper_bound(), 4_294_967_295);
+        assert_eq!(Bits::ThirtyTwo.raw_bits(-1, NumberMode::Signed), 0xFFFF_FFFF);
+        assert_eq!(Bits::ThirtyTwo.raw_bits(i64::from(i32::MIN), NumberMode::Signed), 0x8000_0000);
+
+        let config = PuzzleConfig::new(Bits::ThirtyTwo, NumberMode::Signed)
+            .with_conversion(Conversion::BinaryToHex);
+        let p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        assert_eq!(p.format_answer(-2), "0xFFFFFFFE");
+        assert_eq!(p.format_value(-2, Base::Decimal), "-2");
+        for &s in &p.suggestions {
+            assert!((i64::from(i32::MIN)..=i64::from(i32::MAX)).contains(&s));
+        }
+
+        let config = PuzzleConfig::new(Bits::ThirtyTwo, NumberMode::Unsigned)
+            .with_answer_mode(AnswerMode::FreeEntry);
+        let mut p = BinaryNumbersPuzzle::new(&config, 0, &mut rand::rng());
+        p.suggestions[0] = 4_294_967_295;
+        for c in "4294967295".chars() {
+            p.push_entry_char(c);
+        }
+        assert!(p.entry_is_correct());
+    }
+
+    #[test]
+    fn wide_binary_wraps_between_groups() {
+        let binary = grouped_binary(0xDEAD_BEEF, 32, 4);
+        assert_eq!(binary, "1101 1110 1010 1101 1011 1110 1110 1111");
+        assert_eq!(wrap_groups(&binary, 39), vec![binary.clone()]);
+        assert_eq!(wrap_groups(&binary, 20), ["1101 1110 1010 1101", "1011 1110 1110 1111"]);
+    }
+
     #[test]
     fn puzzle_timeout_sets_guess_result() {
-        let mut p = BinaryNumbersPuzzle::new(Bits::Four, NumberMode::Unsigned, 0);
-        p.time_left = 0.5;
+        let mut p = BinaryNumbersPuzzle::new(
+            &PuzzleConfig::new(Bits::Four, NumberMode::Unsigned),
+            0,
+            &mut rand::rng(),
+        );
+        p.round.time_left = 0.5;
         // First run() skips dt due to skip_first_dt flag
         // The reason for this is to prevent timer jump when starting a new puzzle
-        p.run(1.0);
-        assert_eq!(p.guess_result, None, "First run should skip dt");
+        p.round.run(1.0);
+        assert_eq!(p.round.guess_result, None, "First run should skip dt");
         // Second run() actually applies the dt and triggers timeout
-        p.run(1.0); // exceed remaining time
-        assert_eq!(p.guess_result, Some(GuessResult::Timeout));
+        p.round.run(1.0); // exceed remaining time
+        assert_eq!(p.round.guess_result, Some(GuessResult::Timeout));
     }
 
     #[test]
     fn finalize_round_correct_increments_score_streak_and_sets_result_state() {
         with_high_score_file(|| {
-            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            let mut g: BinaryNumbersGame =
+                BinaryNumbersGame::new(PuzzleConfig::new(Bits::Four, NumberMode::Unsigned));
             // ensure deterministic: mark puzzle correct
-            let answer = g.puzzle.current_number as i32;
-            g.puzzle.guess_result = Some(GuessResult::Correct);
+            let answer = g.puzzle.current_number as i64;
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
             g.finalize_round();
             assert_eq!(g.streak, 1);
             assert_eq!(g.score, 10); // base points
-            assert_eq!(g.puzzle.last_points_awarded, 10);
+            assert_eq!(g.puzzle.round.last_points_awarded, 10);
             assert_eq!(g.game_state, GameState::Result);
             assert!(g.puzzle_resolved);
             assert!(g.puzzle.is_correct_guess(answer));
@@ -1104,10 +2985,13 @@ mod tests {
     #[test]
     fn life_awarded_every_five_streak() {
         with_high_score_file(|| {
-            let mut g = BinaryNumbersGame::new_with_max_lives(Bits::Four, NumberMode::Unsigned, 3);
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new_with_max_lives(
+                PuzzleConfig::new(Bits::Four, NumberMode::Unsigned),
+                3,
+            );
             g.lives = 2; // below max
             g.streak = 4; // about to become 5
-            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
             g.finalize_round();
             assert_eq!(g.streak, 5);
             assert_eq!(g.lives, 3); // gained life
@@ -1117,10 +3001,11 @@ mod tests {
     #[test]
     fn incorrect_guess_resets_streak_and_loses_life() {
         with_high_score_file(|| {
-            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            let mut g: BinaryNumbersGame =
+                BinaryNumbersGame::new(PuzzleConfig::new(Bits::Four, NumberMode::Unsigned));
             g.streak = 3;
             let lives_before = g.lives;
-            g.puzzle.guess_result = Some(GuessResult::Incorrect);
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
             g.finalize_round();
             assert_eq!(g.streak, 0);
             assert_eq!(g.lives, lives_before - 1);
@@ -1130,9 +3015,10 @@ mod tests {
     #[test]
     fn pending_game_over_when_life_reaches_zero() {
         with_high_score_file(|| {
-            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            let mut g: BinaryNumbersGame =
+                BinaryNumbersGame::new(PuzzleConfig::new(Bits::Four, NumberMode::Unsigned));
             g.lives = 1;
-            g.puzzle.guess_result = Some(GuessResult::Incorrect);
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
             g.finalize_round();
             assert_eq!(g.lives, 0);
             assert_eq!(g.game_state, GameState::PendingGameOver);
@@ -1142,12 +3028,13 @@ mod tests {
     #[test]
     fn high_score_updates_and_flag_set() {
         with_high_score_file(|| {
-            let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+            let mut g: BinaryNumbersGame =
+                BinaryNumbersGame::new(PuzzleConfig::new(Bits::Four, NumberMode::Unsigned));
             // Force previous high score low
-            let key = BinaryNumbersGame::compute_high_score_key(&g.bits, g.number_mode);
+            let key = g.config.high_score_key();
             g.high_scores.update(&key, 5);
             g.prev_high_score_for_display = 5;
-            g.puzzle.guess_result = Some(GuessResult::Correct);
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
             g.finalize_round();
             assert!(g.new_high_score_reached);
             assert!(g.high_scores.get(&key) >= 10);
@@ -1157,14 +3044,349 @@ mod tests {
 
     #[test]
     fn hearts_representation_matches_lives() {
-        let mut g = BinaryNumbersGame::new_with_max_lives(Bits::Four, NumberMode::Unsigned, 3);
+        let mut g: BinaryNumbersGame = BinaryNumbersGame::new_with_max_lives(
+            PuzzleConfig::new(Bits::Four, NumberMode::Unsigned),
+            3,
+        );
         g.lives = 2;
         assert_eq!(g.lives_hearts(), "♥♥·");
     }
 
+    #[test]
+    fn starting_lives_are_not_clamped_to_three() {
+        let mut g: BinaryNumbersGame = BinaryNumbersGame::new_with_max_lives(
+            PuzzleConfig::new(Bits::Four, NumberMode::Unsigned),
+            5,
+        );
+        assert_eq!(g.lives_hearts(), "♥♥♥♥♥");
+        g.lives = 0;
+        g.reset_game_state();
+        assert_eq!(g.lives, 5);
+    }
+
+    #[test]
+    fn each_lives_setting_has_its_own_high_score_key() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned);
+        assert_eq!(config.high_score_key(), "8u");
+        assert_eq!(config.clone().with_lives(Lives::SuddenDeath).high_score_key(), "8u-1life");
+        assert_eq!(config.clone().with_lives(Lives::Endless).high_score_key(), "8u-endless");
+        // only games with lives care about them
+        let timed = config.with_game_mode(GameMode::TimeAttack).with_lives(Lives::SuddenDeath);
+        assert_eq!(timed.high_score_key(), "8u-60s");
+    }
+
+    #[test]
+    fn sudden_death_ends_at_the_first_miss_and_endless_never_does() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Four, NumberMode::Unsigned);
+            let mut g: BinaryNumbersGame =
+                BinaryNumbersGame::new(config.clone().with_lives(Lives::SuddenDeath));
+            assert_eq!(g.lives_hearts(), "♥");
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            assert_eq!(g.game_state, GameState::PendingGameOver);
+
+            let mut g: BinaryNumbersGame =
+                BinaryNumbersGame::new(config.with_lives(Lives::Endless));
+            for _ in 0..10 {
+                g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
+                g.finalize_round();
+                assert_eq!(g.game_state, GameState::Result);
+                g.handle_game_input(key(KeyCode::Enter));
+            }
+            assert_eq!(g.lives_hearts(), "∞");
+        });
+    }
+
+    #[test]
+    fn mixed_rounds_share_one_high_score_and_pay_for_difficulty() {
+        with_high_score_file(|| {
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(mixed::config(Some(7)));
+            assert_eq!(g.config.high_score_key(), "mixed");
+            g.streak = 29;
+            g.puzzle =
+                BinaryNumbersGame::init_puzzle(&mut g.config, g.streak, &mut g.rng, &g.weaknesses);
+            assert!(g.config.bits.to_int() >= 12);
+            assert_eq!(g.config.high_score_key(), "mixed");
+            assert!(g.config.mode_label().starts_with("MIXED "));
+
+            let difficulty = mixed::difficulty(&g.config.bits, g.config.number_mode);
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!(g.score, 10 * difficulty + 29 * 2);
+            assert_eq!(g.high_scores.get("mixed"), g.score);
+        });
+    }
+
+    #[test]
+    fn misses_are_remembered_and_asked_again() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Eight, NumberMode::Signed);
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config.clone());
+            g.puzzle.raw_current_number = 0b1010_0101;
+            g.puzzle.suggestions[0] = NumberMode::Signed.value_of(0b1010_0101, 8);
+            // one off, so only the lowest bit was wrong
+            g.puzzle.selected_suggestion = Some(NumberMode::Signed.value_of(0b1010_0100, 8));
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            assert_eq!(
+                fs::read_to_string(Weaknesses::path()).ok().as_deref(),
+                Some("8s value 165=2\n8s bit 0=1\n")
+            );
+
+            let asked_again = (0..200)
+                .filter(|_| {
+                    g.reset_game_state();
+                    g.puzzle.raw_current_number == 0b1010_0101
+                })
+                .count();
+            assert!(asked_again > 50, "{asked_again}");
+            // seeded games stay the same for everyone
+            assert!(!config.with_seed(Some(1)).is_adaptive());
+        });
+    }
+
+    #[test]
+    fn gray_misses_are_remembered_by_the_plain_bit() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned)
+                .with_conversion(Conversion::BinaryToGray);
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config);
+            g.puzzle.raw_current_number = 0b1010_0101;
+            // bit 3 wrong, which shows as bits 2 and 3 in the Gray code
+            g.puzzle.selected_suggestion = Some(0b1010_1101);
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            assert_eq!(
+                fs::read_to_string(Weaknesses::path()).ok().as_deref(),
+                Some("8u value 165=2\n8u bit 3=1\n")
+            );
+        });
+        for raw in [0, 1, 0b1010_0101, u64::MAX] {
+            assert_eq!(gray_decode(gray_encode(raw)), raw);
+        }
+    }
+
+    #[test]
+    fn seeded_games_replay_the_same_numbers() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned).with_seed(Some(42));
+        let answers = |g: &mut BinaryNumbersGame| -> Vec<i64> {
+            let mut answers = vec![g.puzzle.suggestions[0]];
+            for _ in 0..4 {
+                g.puzzle =
+                    BinaryNumbersGame::init_puzzle(&mut g.config, 0, &mut g.rng, &g.weaknesses);
+                answers.push(g.puzzle.suggestions[0]);
+            }
+            answers
+        };
+        let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config.clone());
+        let first = answers(&mut g);
+        assert_eq!(first, [174, 164, 137, 186, 68]);
+        assert_eq!(answers(&mut BinaryNumbersGame::new(config)), first);
+        // starting over replays the game from the top
+        g.reset_game_state();
+        assert_eq!(answers(&mut g), first);
+    }
+
+    #[test]
+    fn daily_challenge_plays_every_round_and_is_scored_once() {
+        with_high_score_file(|| {
+            let config = daily::config(10_957); // 2000-01-01
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config.clone());
+            assert!(g.scored);
+            for round in 0..daily::ROUNDS {
+                g.puzzle.round.guess_result = Some(if round % 2 == 0 {
+                    GuessResult::Correct
+                } else {
+                    GuessResult::Incorrect
+                });
+                g.finalize_round();
+                g.handle_game_input(key(KeyCode::Enter));
+            }
+            // wrong answers cost no lives, the game ends after the last round
+            assert_eq!(g.lives, 3);
+            assert_eq!(g.game_state, GameState::GameOver);
+            assert_eq!(g.round_results.len(), daily::ROUNDS as usize);
+            let score = g.score;
+            assert_eq!(HighScores::load().daily.get("2000-01-01"), Some(&score));
+
+            let mut replay: BinaryNumbersGame = BinaryNumbersGame::new(config);
+            assert!(!replay.scored);
+            replay.puzzle.round.guess_result = Some(GuessResult::Correct);
+            replay.finalize_round();
+            assert_eq!(HighScores::load().daily.get("2000-01-01"), Some(&score));
+        });
+    }
+
+    #[test]
+    fn daily_scores_are_kept_in_their_own_section() {
+        with_high_score_file(|| {
+            let mut hs = HighScores::empty();
+            hs.update("8u", 50);
+            hs.record_daily("2026-10-17", 120);
+            assert!(hs.save().is_ok());
+            let contents = fs::read_to_string(storage::high_scores()).unwrap_or_default();
+            assert!(contents.ends_with("[daily]\n2026-10-17=120\n"));
+            let loaded = HighScores::load();
+            assert_eq!(loaded.get("8u"), 50);
+            assert_eq!(loaded.get("2026-10-17"), 0);
+            assert_eq!(loaded.daily.get("2026-10-17"), Some(&120));
+        });
+    }
+
+    #[test]
+    fn time_attack_moves_straight_on_and_wrong_answers_cost_time() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Eight, NumberMode::Unsigned)
+                .with_game_mode(GameMode::TimeAttack);
+            assert_eq!(config.high_score_key(), "8u-60s");
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config);
+
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!(g.game_state, GameState::Active);
+            assert_eq!(g.puzzle.round.guess_result, None);
+            assert_eq!(g.previous_outcome, Some((GuessResult::Correct, 10)));
+
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            assert_eq!(g.lives, 3);
+            assert_eq!(g.countdown.as_ref().map(|c| c.time_left), Some(55.0));
+
+            g.handle_game_input(key(KeyCode::Char('s')));
+            assert_eq!(g.game_state, GameState::Active);
+            assert_eq!(g.previous_outcome, Some((GuessResult::Timeout, 0)));
+            assert_eq!(g.countdown.as_ref().map(|c| c.time_left), Some(50.0));
+
+            // the first frame is skipped like in every round
+            g.run(0.5);
+            g.run(45.0);
+            assert_eq!(g.game_state, GameState::Active);
+            g.run(5.0);
+            assert_eq!(g.game_state, GameState::GameOver);
+            assert_eq!(g.time_taken, 50.0);
+        });
+    }
+
+    #[test]
+    fn time_attack_ends_when_a_penalty_uses_up_the_clock() {
+        with_high_score_file(|| {
+            let config = PuzzleConfig::new(Bits::Four, NumberMode::Unsigned)
+                .with_game_mode(GameMode::TimeAttack);
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config);
+            if let Some(countdown) = &mut g.countdown {
+                countdown.time_left = 3.0;
+            }
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            assert_eq!(g.game_state, GameState::GameOver);
+        });
+    }
+
+    #[test]
+    fn zen_practice_has_no_timer_lives_or_high_scores() {
+        with_high_score_file(|| {
+            let config =
+                PuzzleConfig::new(Bits::Eight, NumberMode::Signed).with_game_mode(GameMode::Zen);
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(config);
+            g.run(0.5);
+            g.run(100.0);
+            assert_eq!(g.puzzle.round.guess_result, None);
+            assert_eq!(g.puzzle.round.time_left, g.puzzle.round.time_total);
+
+            g.handle_game_input(key(KeyCode::Char('?')));
+            g.handle_game_input(key(KeyCode::Char('?')));
+            g.handle_game_input(key(KeyCode::Char('?')));
+            assert_eq!(g.puzzle.round.hints_shown, 2);
+
+            g.puzzle.round.guess_result = Some(GuessResult::Incorrect);
+            g.finalize_round();
+            assert_eq!(g.lives, 3);
+            assert_eq!(g.game_state, GameState::Result);
+            g.handle_game_input(key(KeyCode::Enter));
+            assert_eq!(g.puzzle.round.hints_shown, 0);
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert_eq!(g.accuracy(), (1, 2));
+            assert!(!g.new_high_score_reached);
+            assert!(!fs::exists(storage::high_scores()).unwrap_or(true));
+        });
+    }
+
+    #[test]
+    fn study_asks_the_due_cards_and_schedules_them() {
+        with_high_score_file(|| {
+            let mut g: BinaryNumbersGame = BinaryNumbersGame::new(flashcards::config());
+            assert_eq!(g.round_limit(), Some(10));
+            assert_eq!(g.config.bits, Bits::Four);
+            assert_eq!(g.config.conversion, Conversion::BinaryToDecimal);
+            assert_eq!(g.puzzle.raw_current_number, 0);
+
+            g.puzzle.round.guess_result = Some(GuessResult::Correct);
+            g.finalize_round();
+            assert!(!g.new_high_score_reached);
+            let today = daily::today();
+            assert_eq!(
+                fs::read_to_string(flashcards::Deck::path()).ok(),
+                Some(format!("4 0=1 1 2.60 {} {today}\n", today + 1))
+            );
+
+            g.handle_game_input(key(KeyCode::Enter));
+            assert_eq!(g.config.conversion, Conversion::DecimalToBinary);
+            assert_eq!(g.puzzle.raw_current_number, 0);
+            // the first card is no longer new today
+            assert_eq!(flashcards::due_today(), 9);
+        });
+    }
+
+    #[test]
+    fn hints_give_place_values_then_a_partial_sum() {
+        let hints_for = |bits: Bits, number_mode: NumberMode, raw: u64| {
+            let mut p = BinaryNumbersPuzzle::new(
+                &PuzzleConfig::new(bits, number_mode),
+                0,
+                &mut rand::rng(),
+            );
+            p.raw_current_number = raw;
+            p.hints()
+        };
+        assert_eq!(
+            hints_for(Bits::Eight, NumberMode::Signed, 0b1010_0110),
+            ["-128 64 32 16 8 4 2 1", "top 4 bits make -96"]
+        );
+        assert_eq!(
+            hints_for(Bits::Eight, NumberMode::SignMagnitude, 0b1010_0110),
+            ["± 64 32 16 8 4 2 1", "top 4 bits make -32"]
+        );
+        assert_eq!(
+            hints_for(Bits::Eight, NumberMode::Excess, 0b1010_0110),
+            ["128 64 32 16 8 4 2 1 -127", "top 4 bits make 33"]
+        );
+        assert_eq!(
+            hints_for(Bits::FourShift4, NumberMode::OnesComplement, 0b1001),
+            ["-112 64 32 16", "top 2 bits make -112"]
+        );
+        assert_eq!(hints_for(Bits::Sixteen, NumberMode::Unsigned, 0)[0], "32768 16384 … 8 4 2 1");
+        // too few places to leave any out, however long they are
+        let custom = |width, shift| Bits::Custom(CustomBits { width, shift, suggestions: 4 });
+        assert_eq!(
+            hints_for(custom(3, 29), NumberMode::Unsigned, 0b101),
+            ["2147483648 1073741824 536870912", "top 1 bits make 2147483648"]
+        );
+        assert_eq!(
+            hints_for(custom(3, 29), NumberMode::Signed, 0b101),
+            ["-2147483648 1073741824 536870912", "top 1 bits make -2147483648"]
+        );
+        assert_eq!(
+            hints_for(custom(4, 28), NumberMode::Unsigned, 0)[0],
+            "2147483648 1073741824 536870912 268435456"
+        );
+    }
+
     #[test]
     fn handle_input_navigation_changes_selected_suggestion() {
-        let mut g = BinaryNumbersGame::new(Bits::Four, NumberMode::Unsigned);
+        let mut g: BinaryNumbersGame =
+            BinaryNumbersGame::new(PuzzleConfig::new(Bits::Four, NumberMode::Unsigned));
         let initial = g.puzzle.selected_suggestion;
         // Simulate Right key
         let right_event = KeyEvent {
@@ -0,0 +1,303 @@
+use crate::app::NumberMode;
+use crate::binary_numbers::{PuzzleConfig, grouped_binary};
+use crate::keybinds;
+use crate::main_screen_widget::WidgetRef;
+use crate::puzzle::{Choices, Puzzle, Round, render_centered_prompt, step_selection};
+use crossterm::event::KeyEvent;
+use rand::Rng;
+use rand::prelude::{IndexedRandom, SliceRandom};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Style};
+use ratatui::text::Span;
+
+/// Bit operators a bitwise puzzle asks about
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BitOp {
+    And,
+    Or,
+    Xor,
+    Not,
+    ShiftLeft,
+    /// Logical shift, zeros come in from the left
+    ShiftRight,
+    /// Arithmetic shift, copies of the sign bit come in from the left
+    ArithmeticShiftRight,
+}
+
+impl BitOp {
+    const UNSIGNED: [Self; 6] =
+        [Self::And, Self::Or, Self::Xor, Self::Not, Self::ShiftLeft, Self::ShiftRight];
+    const SIGNED: [Self; 7] = [
+        Self::And,
+        Self::Or,
+        Self::Xor,
+        Self::Not,
+        Self::ShiftLeft,
+        Self::ShiftRight,
+        Self::ArithmeticShiftRight,
+    ];
+
+    /// Right shifts are written like in Java, so signed mode can tell `>>` and `>>>` apart
+    pub const fn symbol(self, number_mode: NumberMode) -> &'static str {
+        match (self, number_mode) {
+            (Self::And, _) => "&",
+            (Self::Or, _) => "|",
+            (Self::Xor, _) => "^",
+            (Self::Not, _) => "~",
+            (Self::ShiftLeft, _) => "<<",
+            (Self::ShiftRight, NumberMode::Signed) => ">>>",
+            (Self::ShiftRight | Self::ArithmeticShiftRight, _) => ">>",
+        }
+    }
+
+    pub const fn is_shift(self) -> bool {
+        matches!(self, Self::ShiftLeft | Self::ShiftRight | Self::ArithmeticShiftRight)
+    }
+
+    /// Result of the operator on `num_bits` wide operands, wrapped to the same width.
+    /// For shifts `right` is the shift distance.
+    pub const fn apply(self, left: u64, right: u64, num_bits: u32) -> u64 {
+        let mask = (1u64 << num_bits) - 1;
+        match self {
+            Self::And => left & right,
+            Self::Or => left | right,
+            Self::Xor => left ^ right,
+            Self::Not => !left & mask,
+            Self::ShiftLeft => (left << right) & mask,
+            Self::ShiftRight => left >> right,
+            Self::ArithmeticShiftRight => {
+                let sign_fill = if left >> (num_bits - 1) & 1 == 1 {
+                    mask & !(mask >> right)
+                } else {
+                    0
+                };
+                (left >> right) | sign_fill
+            },
+        }
+    }
+}
+
+/// Shows one or two operands in binary and an operator, the player picks the result.
+/// Only the width of the selected `Bits` matters, scaled nibbles are plain 4 bit words here.
+pub struct BitwisePuzzle {
+    num_bits: u32,
+    number_mode: NumberMode,
+    op: BitOp,
+    left: u64,
+    right: u64, // second operand, or the shift distance
+    answer: u64,
+    suggestions: Vec<u64>,
+    selected_suggestion: Option<u64>,
+    round: Round,
+}
+
+impl Puzzle for BitwisePuzzle {
+    fn new(config: &PuzzleConfig, streak: u32, rng: &mut impl Rng) -> Self {
+        let num_bits = config.bits.to_int();
+        let mask = config.bits.mask();
+        let number_mode = config.number_mode;
+
+        let ops: &[BitOp] = match number_mode {
+            // the arithmetic shift copies the sign bit of two's complement
+            NumberMode::Signed => &BitOp::SIGNED,
+            _ => &BitOp::UNSIGNED,
+        };
+        let op = *ops.choose(rng).unwrap_or(&BitOp::And);
+        let left = rng.random_range(0..=mask);
+        let right = if op.is_shift() {
+            rng.random_range(1..=u64::from(num_bits - 1).clamp(1, 4))
+        } else {
+            rng.random_range(0..=mask)
+        };
+        let answer = op.apply(left, right, num_bits);
+
+        // Results of the operators that are easily mixed up make the best distractors
+        let mut near_misses = match op {
+            BitOp::And | BitOp::Or | BitOp::Xor => vec![
+                BitOp::And.apply(left, right, num_bits),
+                BitOp::Or.apply(left, right, num_bits),
+                BitOp::Xor.apply(left, right, num_bits),
+                !(left & right) & mask,
+            ],
+            BitOp::Not => vec![left, (BitOp::Not.apply(left, 0, num_bits) + 1) & mask],
+            BitOp::ShiftLeft | BitOp::ShiftRight | BitOp::ArithmeticShiftRight => vec![
+                BitOp::ShiftLeft.apply(left, right, num_bits),
+                BitOp::ShiftRight.apply(left, right, num_bits),
+                BitOp::ArithmeticShiftRight.apply(left, right, num_bits),
+                op.apply(left, right - 1, num_bits),
+                op.apply(left, right + 1, num_bits),
+            ],
+        };
+        near_misses.shuffle(rng);
+
+        let mut suggestions = vec![answer];
+        for candidate in near_misses {
+            if suggestions.len() < config.bits.suggestion_count()
+                && !suggestions.contains(&candidate)
+            {
+                suggestions.push(candidate);
+            }
+        }
+        while suggestions.len() < config.bits.suggestion_count() {
+            let candidate = rng.random_range(0..=mask);
+            if !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+            }
+        }
+        suggestions.shuffle(rng);
+
+        Self {
+            num_bits,
+            number_mode,
+            op,
+            left,
+            right,
+            answer,
+            selected_suggestion: Some(suggestions[0]),
+            suggestions,
+            round: Round::new(streak),
+        }
+    }
+
+    fn round(&self) -> &Round {
+        &self.round
+    }
+
+    fn round_mut(&mut self) -> &mut Round {
+        &mut self.round
+    }
+
+    fn handle_answer_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_right(x) || keybinds::is_down(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, true);
+            },
+            x if keybinds::is_left(x) || keybinds::is_up(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, false);
+            },
+            x if keybinds::is_select(x) => {
+                if let Some(selected) = self.selected_suggestion {
+                    self.round.resolve(selected == self.answer);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn answer_height(&self, width: u16) -> u16 {
+        self.choices().height(width)
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        render_centered_prompt(self.prompt_lines(), area, buf);
+    }
+
+    fn render_answer(&self, area: Rect, buf: &mut Buffer) {
+        self.choices().render_ref(area, buf);
+    }
+
+    fn answer_hint(&self, width: u16) -> (&'static str, &'static str) {
+        self.choices().hint(width)
+    }
+}
+
+impl BitwisePuzzle {
+    fn binary(&self, raw_bits: u64) -> String {
+        grouped_binary(raw_bits, self.num_bits, 4)
+    }
+
+    /// The operation as it would be written in code, with the operands in binary
+    fn prompt_lines(&self) -> Vec<Line<'static>> {
+        let operator =
+            Span::styled(self.op.symbol(self.number_mode), Style::default().fg(Color::LightCyan));
+        let note = |text: &'static str| {
+            Line::from(Span::styled(text, Style::default().fg(Color::DarkGray)))
+        };
+        match self.op {
+            BitOp::And | BitOp::Or | BitOp::Xor => vec![
+                Line::from(format!("    {}", self.binary(self.left))),
+                Line::from(vec![
+                    operator,
+                    Span::raw(" ".repeat(4 - self.op.symbol(self.number_mode).len())),
+                    Span::raw(self.binary(self.right)),
+                ]),
+            ],
+            BitOp::Not => {
+                vec![Line::from(vec![operator, Span::raw(format!(" {}", self.binary(self.left)))])]
+            },
+            BitOp::ShiftLeft | BitOp::ShiftRight | BitOp::ArithmeticShiftRight => {
+                let mut lines = vec![Line::from(vec![
+                    Span::raw(format!("{} ", self.binary(self.left))),
+                    operator,
+                    Span::raw(format!(" {}", self.right)),
+                ])];
+                match (self.op, self.number_mode) {
+                    (BitOp::ArithmeticShiftRight, _) => {
+                        lines.push(note("arithmetic: copies of the sign bit come in"));
+                    },
+                    (BitOp::ShiftRight, NumberMode::Signed) => {
+                        lines.push(note("logical: zeros come in"));
+                    },
+                    _ => {},
+                }
+                lines
+            },
+        }
+    }
+
+    fn choices(&self) -> Choices {
+        Choices {
+            labels: self.suggestions.iter().map(|&s| self.binary(s)).collect(),
+            selected: self
+                .selected_suggestion
+                .and_then(|selected| self.suggestions.iter().position(|&s| s == selected)),
+            correct: self.suggestions.iter().position(|&s| s == self.answer).unwrap_or(0),
+            result: self.round.guess_result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_numbers::{Bits, PuzzleKind};
+    use crate::puzzle::{assert_unique, seeded_puzzles};
+
+    #[test]
+    fn operators_wrap_to_the_word_width() {
+        assert_eq!(BitOp::And.apply(0b1010, 0b0110, 4), 0b0010);
+        assert_eq!(BitOp::Or.apply(0b1010, 0b0110, 4), 0b1110);
+        assert_eq!(BitOp::Xor.apply(0b1010, 0b0110, 4), 0b1100);
+        assert_eq!(BitOp::Not.apply(0b1010, 0, 4), 0b0101);
+        assert_eq!(BitOp::Not.apply(0, 0, 16), 0xFFFF);
+        assert_eq!(BitOp::ShiftLeft.apply(0b1011, 2, 4), 0b1100);
+        assert_eq!(BitOp::ShiftLeft.apply(0b1000_0001, 1, 8), 0b0000_0010);
+    }
+
+    #[test]
+    fn right_shifts_differ_only_for_a_set_sign_bit() {
+        assert_eq!(BitOp::ShiftRight.apply(0b1001_0000, 2, 8), 0b0010_0100);
+        assert_eq!(BitOp::ArithmeticShiftRight.apply(0b1001_0000, 2, 8), 0b1110_0100);
+        assert_eq!(BitOp::ArithmeticShiftRight.apply(0b0101_0000, 2, 8), 0b0001_0100);
+        assert_eq!(BitOp::ShiftRight.symbol(NumberMode::Signed), ">>>");
+        assert_eq!(BitOp::ShiftRight.symbol(NumberMode::Unsigned), ">>");
+        assert_eq!(BitOp::ArithmeticShiftRight.symbol(NumberMode::Signed), ">>");
+    }
+
+    #[test]
+    fn puzzle_offers_unique_suggestions_including_the_result() {
+        let config =
+            PuzzleConfig::new(Bits::Sixteen, NumberMode::Signed).with_kind(PuzzleKind::Bitwise);
+        assert_eq!(config.high_score_key(), "16s-bitwise");
+        for p in seeded_puzzles::<BitwisePuzzle>(&config) {
+            assert_eq!(p.answer, p.op.apply(p.left, p.right, 16));
+            assert_eq!(p.suggestions.len(), Bits::Sixteen.suggestion_count());
+            assert!(p.suggestions.contains(&p.answer));
+            assert_unique(&p.suggestions);
+            assert!(p.suggestions.iter().all(|&s| s <= 0xFFFF));
+        }
+    }
+}
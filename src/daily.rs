@@ -0,0 +1,112 @@
+use crate::app::NumberMode;
+use crate::binary_numbers::{Bits, GameMode, PuzzleConfig};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Length of a daily challenge, every round is played
+pub const ROUNDS: u32 = 20;
+
+/// Width and number mode of the daily puzzles, shown in the start menu before the game starts
+pub const BITS: Bits = Bits::Eight;
+pub const NUMBER_MODE: NumberMode = NumberMode::Unsigned;
+
+/// Days since 1970-01-01. The day changes at midnight UTC, so it is the same everywhere.
+pub fn today() -> u64 {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    seconds / 86_400
+}
+
+/// The `YYYY-MM-DD` date of a day counted from 1970-01-01,
+/// after Howard Hinnant's `civil_from_days`
+pub fn date_of_day(days: u64) -> String {
+    // shift the epoch to 0000-03-01, so leap days come last in the 400 year era
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// FNV-1a hash of the date, stable across platforms and builds unlike the std hasher
+fn seed_for(date: &str) -> u64 {
+    date.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3)
+    })
+}
+
+/// Everyone gets the same puzzles on the same day: classic byte conversions drawn from the date
+pub fn config(day: u64) -> PuzzleConfig {
+    PuzzleConfig::new(BITS, NUMBER_MODE)
+        .with_seed(Some(seed_for(&date_of_day(day))))
+        .with_game_mode(GameMode::Daily { day })
+}
+
+/// A result to paste into a chat: the date, the tally and the time taken,
+/// followed by one ✓ or ✗ per round in rows of ten
+pub fn share_lines(date: &str, results: &[bool], seconds: f64) -> Vec<String> {
+    let correct = results.iter().filter(|&&correct| correct).count();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let seconds = seconds.max(0.0).round() as u64;
+    let mut lines = vec![format!(
+        "binbreak {date}  {correct}/{}  {}:{:02}",
+        results.len(),
+        seconds / 60,
+        seconds % 60
+    )];
+    lines.extend(
+        results
+            .chunks(10)
+            .map(|row| row.iter().map(|&correct| if correct { '✓' } else { '✗' }).collect()),
+    );
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    #[test]
+    fn days_count_from_the_unix_epoch() {
+        assert_eq!(date_of_day(0), "1970-01-01");
+        assert_eq!(date_of_day(11_016), "2000-02-29");
+        assert_eq!(date_of_day(19_723), "2024-01-01");
+        assert_eq!(date_of_day(20_743), "2026-10-17");
+    }
+
+    #[test]
+    fn each_day_has_its_own_seed() {
+        assert_eq!(seed_for("2026-10-17"), seed_for("2026-10-17"));
+        assert_ne!(seed_for("2026-10-17"), seed_for("2026-10-18"));
+        assert_eq!(config(20_743).seed, Some(seed_for("2026-10-17")));
+    }
+
+    #[test]
+    fn the_numbers_of_a_day_never_change() {
+        let mut rng = config(20_743).rng();
+        let raw: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+        assert_eq!(
+            raw,
+            [11_902_000_671_998_328_966, 5_420_923_314_120_894_824, 5_036_107_197_715_173_258]
+        );
+    }
+
+    #[test]
+    fn share_lines_tally_the_rounds_in_rows_of_ten() {
+        let mut results = vec![true; 12];
+        results[3] = false;
+        assert_eq!(
+            share_lines("2026-10-17", &results, 83.4),
+            ["binbreak 2026-10-17  11/12  1:23", "✓✓✓✗✓✓✓✓✓✓", "✓✓"]
+        );
+    }
+}
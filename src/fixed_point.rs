@@ -0,0 +1,213 @@
+use crate::app::NumberMode;
+use crate::binary_numbers::{PuzzleConfig, grouped_binary};
+use crate::keybinds;
+use crate::main_screen_widget::WidgetRef;
+use crate::puzzle::{Choices, Puzzle, Round, render_centered_prompt, step_selection};
+use crossterm::event::KeyEvent;
+use rand::Rng;
+use rand::prelude::SliceRandom;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Style};
+use ratatui::text::Span;
+
+/// Shows a bit pattern with a binary point in it, the player picks its fractional value.
+/// Values are kept as the integer the pattern stands for, to be divided by 2^fraction_bits.
+pub struct FixedPointPuzzle {
+    num_bits: u32,
+    fraction_bits: u32,
+    number_mode: NumberMode,
+    raw: u64,
+    suggestions: Vec<i64>,
+    selected_suggestion: Option<i64>,
+    round: Round,
+}
+
+impl Puzzle for FixedPointPuzzle {
+    fn new(config: &PuzzleConfig, streak: u32, rng: &mut impl Rng) -> Self {
+        let num_bits = config.bits.to_int();
+        let mask = config.bits.mask();
+        let number_mode = config.number_mode;
+        let count = usize::try_from(number_mode.distinct_values(num_bits))
+            .map_or(config.bits.suggestion_count(), |distinct| {
+                config.bits.suggestion_count().min(distinct)
+            });
+
+        let raw = rng.random_range(0..=mask);
+        let answer = number_mode.value_of(raw, num_bits);
+
+        // Typical slips: the point one place off, the sign ignored or flipped, the last bit misread
+        let mut near_misses = vec![answer * 2, -answer, answer + 1, answer - 1, raw as i64];
+        if answer % 2 == 0 {
+            near_misses.push(answer / 2);
+        }
+        near_misses.shuffle(rng);
+
+        let mut suggestions = vec![answer];
+        // values out of range would give themselves away
+        let representable = |value: i64| {
+            number_mode.value_of(number_mode.raw_of(value, num_bits), num_bits) == value
+        };
+        for candidate in near_misses {
+            if suggestions.len() < count
+                && !suggestions.contains(&candidate)
+                && representable(candidate)
+            {
+                suggestions.push(candidate);
+            }
+        }
+        while suggestions.len() < count {
+            let candidate = number_mode.value_of(rng.random_range(0..=mask), num_bits);
+            if !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+            }
+        }
+        suggestions.shuffle(rng);
+
+        Self {
+            num_bits,
+            fraction_bits: config.fraction_bits,
+            number_mode,
+            raw,
+            selected_suggestion: Some(suggestions[0]),
+            suggestions,
+            round: Round::new(streak),
+        }
+    }
+
+    fn round(&self) -> &Round {
+        &self.round
+    }
+
+    fn round_mut(&mut self) -> &mut Round {
+        &mut self.round
+    }
+
+    fn handle_answer_input(&mut self, input: KeyEvent) {
+        match input {
+            x if keybinds::is_right(x) || keybinds::is_down(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, true);
+            },
+            x if keybinds::is_left(x) || keybinds::is_up(x) => {
+                self.selected_suggestion =
+                    step_selection(&self.suggestions, self.selected_suggestion, false);
+            },
+            x if keybinds::is_select(x) => {
+                if let Some(selected) = self.selected_suggestion {
+                    self.round.resolve(selected == self.answer());
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn answer_height(&self, width: u16) -> u16 {
+        self.choices().height(width)
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let lines = vec![
+            Line::from(fixed_point_binary(self.raw, self.num_bits, self.fraction_bits)),
+            Line::from(Span::styled(
+                q_format(self.num_bits, self.fraction_bits),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        render_centered_prompt(lines, area, buf);
+    }
+
+    fn render_answer(&self, area: Rect, buf: &mut Buffer) {
+        self.choices().render_ref(area, buf);
+    }
+
+    fn answer_hint(&self, width: u16) -> (&'static str, &'static str) {
+        self.choices().hint(width)
+    }
+}
+
+impl FixedPointPuzzle {
+    fn answer(&self) -> i64 {
+        self.number_mode.value_of(self.raw, self.num_bits)
+    }
+
+    fn choices(&self) -> Choices {
+        Choices {
+            labels: self
+                .suggestions
+                .iter()
+                .map(|&s| format_fixed_point(s, self.fraction_bits))
+                .collect(),
+            selected: self
+                .selected_suggestion
+                .and_then(|selected| self.suggestions.iter().position(|&s| s == selected)),
+            correct: self.suggestions.iter().position(|&s| s == self.answer()).unwrap_or(0),
+            result: self.round.guess_result,
+        }
+    }
+}
+
+/// ARM style Q notation, the integer bits include the sign bit, e.g. Q1.7 for a signed byte
+pub fn q_format(num_bits: u32, fraction_bits: u32) -> String {
+    format!("Q{}.{}", num_bits - fraction_bits, fraction_bits)
+}
+
+/// `value / 2^fraction_bits` with as many decimals as fraction bits, which is always exact
+fn format_fixed_point(value: i64, fraction_bits: u32) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let fractional = value as f64 / f64::from(1u32 << fraction_bits);
+    format!("{fractional:.precision$}", precision = fraction_bits as usize)
+}
+
+/// The pattern with a binary point, both sides grouped in nibbles counted from the point
+fn fixed_point_binary(raw: u64, num_bits: u32, fraction_bits: u32) -> String {
+    let integer = grouped_binary(raw >> fraction_bits, num_bits - fraction_bits, 4);
+    let width = fraction_bits as usize;
+    let mut fraction = String::new();
+    for (i, c) in format!("{:0width$b}", raw & ((1 << fraction_bits) - 1)).chars().enumerate() {
+        if i > 0 && i.is_multiple_of(4) {
+            fraction.push(' ');
+        }
+        fraction.push(c);
+    }
+    format!("{integer}.{fraction}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_numbers::{Bits, PuzzleKind};
+    use crate::puzzle::{assert_unique, seeded_puzzles};
+
+    #[test]
+    fn binary_point_splits_the_pattern() {
+        assert_eq!(fixed_point_binary(0b0101_1100, 8, 4), "0101.1100");
+        assert_eq!(fixed_point_binary(0b1110_0101, 8, 7), "1.1100 101");
+        assert_eq!(fixed_point_binary(0xA5C3, 16, 8), "1010 0101.1100 0011");
+        assert_eq!(q_format(8, 7), "Q1.7");
+    }
+
+    #[test]
+    fn values_are_shown_exactly_at_a_fixed_precision() {
+        assert_eq!(format_fixed_point(NumberMode::Unsigned.value_of(0b0101_1100, 8), 4), "5.7500");
+        assert_eq!(
+            format_fixed_point(NumberMode::Signed.value_of(0b1000_0000, 8), 7),
+            "-1.0000000"
+        );
+        assert_eq!(format_fixed_point(NumberMode::Signed.value_of(0b0000_0001, 8), 7), "0.0078125");
+        assert_eq!(format_fixed_point(0xFFFF, 8), "255.99609375");
+    }
+
+    #[test]
+    fn puzzle_offers_unique_suggestions_including_the_value() {
+        let config = PuzzleConfig::new(Bits::Eight, NumberMode::Signed)
+            .with_kind(PuzzleKind::FixedPoint)
+            .with_fraction_bits(7);
+        assert_eq!(config.high_score_key(), "8s-q1.7");
+        for p in seeded_puzzles::<FixedPointPuzzle>(&config) {
+            assert_eq!(p.suggestions.len(), Bits::Eight.suggestion_count());
+            assert!(p.suggestions.contains(&p.answer()));
+            assert_unique(&p.suggestions);
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use crate::app::NumberMode;
+use crate::binary_numbers::{Bits, Conversion, GameMode, PuzzleConfig};
+use crate::daily;
+use crate::storage;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+/// Cards seen for the first time on one day, the rest wait for the following days
+const NEW_PER_DAY: usize = 10;
+
+/// Ease of a card that was never answered, the factor its interval grows by
+const STARTING_EASE: f64 = 2.5;
+
+/// SM-2 never lets a card get easier to forget than this
+const MINIMUM_EASE: f64 = 1.3;
+
+/// One value in one direction, e.g. the nibble 0101 to be read as a decimal
+#[derive(Clone, PartialEq, Debug)]
+pub struct Card {
+    pub bits: Bits,
+    pub conversion: Conversion,
+    pub raw: u64,
+}
+
+impl Card {
+    /// Stable name in the deck file, e.g. `8-d2b 165`
+    fn id(&self) -> String {
+        format!("{}{} {}", self.bits.to_int(), self.conversion.high_score_suffix(), self.raw)
+    }
+
+    /// Every card in the order new cards are handed out: nibbles before bytes,
+    /// both directions of a value after another
+    fn all() -> Vec<Self> {
+        [Bits::Four, Bits::Eight]
+            .into_iter()
+            .flat_map(|bits| (0..=bits.mask()).map(move |raw| (bits.clone(), raw)))
+            .flat_map(|(bits, raw)| {
+                [Conversion::BinaryToDecimal, Conversion::DecimalToBinary].map(|conversion| Self {
+                    bits: bits.clone(),
+                    conversion,
+                    raw,
+                })
+            })
+            .collect()
+    }
+}
+
+/// When a card is due again and how fast its intervals grow
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Schedule {
+    /// Correct answers in a row
+    repetitions: u32,
+    /// Days between the last review and the next one
+    interval: u32,
+    ease: f64,
+    /// Day of the next review, counted like `daily::today`
+    due: u64,
+    /// Day the card was first answered
+    introduced: u64,
+}
+
+impl Schedule {
+    const fn new(today: u64) -> Self {
+        Self { repetitions: 0, interval: 0, ease: STARTING_EASE, due: today, introduced: today }
+    }
+
+    /// SM-2: a miss starts the card over, a hit multiplies its interval by its ease.
+    /// `quality` runs from 0 (skipped) to 5 (right with time to spare).
+    fn review(&mut self, quality: u32, today: u64) {
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval = 1;
+        } else {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    clippy::cast_precision_loss
+                )]
+                _ => (f64::from(self.interval) * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        }
+        let miss = f64::from(5 - quality.min(5));
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MINIMUM_EASE);
+        self.due = today + u64::from(self.interval);
+    }
+}
+
+/// How well a card was known: misses score below 3, hits score higher the faster they were
+pub fn quality(correct: bool, skipped: bool, share_of_time_left: f64) -> u32 {
+    match (correct, skipped) {
+        (false, true) => 0,
+        (false, false) => 1,
+        (true, _) if share_of_time_left > 0.6 => 5,
+        (true, _) if share_of_time_left > 0.3 => 4,
+        (true, _) => 3,
+    }
+}
+
+/// The schedules of all cards answered so far, kept next to the high scores
+pub struct Deck {
+    schedules: BTreeMap<String, Schedule>,
+}
+
+impl Deck {
+    pub const FILE: &'static str = "binbreak_deck.txt";
+
+    pub fn load() -> Self {
+        Self::parse(&fs::read_to_string(Self::path()).unwrap_or_default())
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        storage::write(&Self::path(), &self.to_text())
+    }
+
+    pub fn path() -> PathBuf {
+        storage::next_to_high_scores(Self::FILE)
+    }
+
+    /// Lines like `8-d2b 165=2 6 2.36 20750 20743`: repetitions, interval, ease, due and
+    /// introduced day. Broken lines are skipped, the card just counts as new again.
+    fn parse(contents: &str) -> Self {
+        let mut schedules = BTreeMap::new();
+        for line in contents.lines() {
+            let Some((id, fields)) = line.split_once('=') else { continue };
+            let fields: Vec<&str> = fields.split_whitespace().collect();
+            let [repetitions, interval, ease, due, introduced] = fields[..] else { continue };
+            let (Ok(repetitions), Ok(interval), Ok(ease), Ok(due), Ok(introduced)) = (
+                repetitions.parse(),
+                interval.parse(),
+                ease.parse(),
+                due.parse(),
+                introduced.parse(),
+            ) else {
+                continue;
+            };
+            let schedule = Schedule { repetitions, interval, ease, due, introduced };
+            schedules.insert(id.trim().to_string(), schedule);
+        }
+        Self { schedules }
+    }
+
+    fn to_text(&self) -> String {
+        let mut data = String::new();
+        for (id, s) in &self.schedules {
+            let _ = writeln!(
+                data,
+                "{id}={} {} {:.2} {} {}",
+                s.repetitions, s.interval, s.ease, s.due, s.introduced
+            );
+        }
+        data
+    }
+
+    /// Cards to review on the given day, the longest overdue first, then the new cards
+    /// that are left for the day
+    pub fn due(&self, today: u64) -> Vec<Card> {
+        let cards = Card::all();
+        let mut reviews: Vec<(u64, &Card)> = cards
+            .iter()
+            .filter_map(|card| self.schedules.get(&card.id()).map(|s| (s.due, card)))
+            .filter(|&(due, _)| due <= today)
+            .collect();
+        reviews.sort_by_key(|&(due, _)| due);
+
+        let introduced_today = self.schedules.values().filter(|s| s.introduced == today).count();
+        let new_cards = cards
+            .iter()
+            .filter(|card| !self.schedules.contains_key(&card.id()))
+            .take(NEW_PER_DAY.saturating_sub(introduced_today));
+        reviews.into_iter().map(|(_, card)| card).chain(new_cards).cloned().collect()
+    }
+
+    fn review(&mut self, card: &Card, quality: u32, today: u64) -> Schedule {
+        let schedule = self.schedules.entry(card.id()).or_insert_with(|| Schedule::new(today));
+        schedule.review(quality, today);
+        *schedule
+    }
+}
+
+/// The cards due when a study game starts, asked one per round
+pub struct Session {
+    deck: Deck,
+    today: u64,
+    queue: VecDeque<Card>,
+    current: Option<Card>,
+    /// Cards due when the session started, the number of rounds
+    total: u32,
+    /// Days until the card answered last comes up again
+    last_interval: Option<u32>,
+}
+
+impl Session {
+    pub fn start() -> Self {
+        let deck = Deck::load();
+        let today = daily::today();
+        let queue: VecDeque<Card> = deck.due(today).into();
+        #[allow(clippy::cast_possible_truncation)]
+        let total = queue.len() as u32;
+        Self { deck, today, queue, current: None, total, last_interval: None }
+    }
+
+    /// Ask about the next due card, if any is left
+    pub fn next_card(&mut self, config: &mut PuzzleConfig) {
+        self.current = self.queue.pop_front();
+        if let Some(card) = &self.current {
+            config.bits = card.bits.clone();
+            config.conversion = card.conversion;
+            config.target = Some(card.raw);
+        }
+    }
+
+    /// Schedule the current card by how it was answered and save the deck
+    pub fn review(&mut self, quality: u32) {
+        if let Some(card) = &self.current {
+            let schedule = self.deck.review(card, quality, self.today);
+            self.last_interval = Some(schedule.interval);
+            let _ = self.deck.save();
+        }
+    }
+
+    pub const fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// Shown after an answer, e.g. "due again in 6 days"
+    pub fn verdict(&self) -> String {
+        match self.last_interval {
+            Some(1) | None => "due again tomorrow".to_string(),
+            Some(days) => format!("due again in {days} days"),
+        }
+    }
+
+    pub fn due_tomorrow(&self) -> usize {
+        self.deck.due(self.today + 1).len()
+    }
+}
+
+/// A study game: the cards of the deck that are due today, one per round
+pub fn config() -> PuzzleConfig {
+    PuzzleConfig::new(Bits::Four, NumberMode::Unsigned).with_game_mode(GameMode::Study)
+}
+
+/// Shown in the start menu before the session starts
+pub fn due_today() -> usize {
+    Deck::load().due(daily::today()).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_grow_with_every_hit_and_reset_on_a_miss() {
+        let mut schedule = Schedule::new(100);
+        let mut intervals = Vec::new();
+        for quality in [5, 5, 5, 4, 1, 3] {
+            schedule.review(quality, 100);
+            intervals.push(schedule.interval);
+        }
+        assert_eq!(intervals, [1, 6, 16, 45, 1, 1]);
+        assert_eq!(schedule.due, 101);
+        assert!(schedule.ease >= MINIMUM_EASE && schedule.ease < STARTING_EASE);
+    }
+
+    #[test]
+    fn answers_score_by_correctness_and_time_left() {
+        assert_eq!(quality(false, true, 0.9), 0);
+        assert_eq!(quality(false, false, 0.9), 1);
+        assert_eq!(quality(true, false, 0.9), 5);
+        assert_eq!(quality(true, false, 0.5), 4);
+        assert_eq!(quality(true, false, 0.1), 3);
+    }
+
+    #[test]
+    fn new_cards_are_handed_out_a_few_a_day() {
+        let mut deck = Deck::parse("");
+        let due = deck.due(100);
+        assert_eq!(due.len(), NEW_PER_DAY);
+        assert_eq!(due[0].id(), "4 0");
+        assert_eq!(due[1].id(), "4-d2b 0");
+
+        for card in &due[..4] {
+            deck.review(card, 5, 100);
+        }
+        assert_eq!(deck.due(100).len(), NEW_PER_DAY - 4);
+        // tomorrow the reviews come first, then a fresh batch of new cards
+        let tomorrow = deck.due(101);
+        assert_eq!(tomorrow.len(), 4 + NEW_PER_DAY);
+        assert_eq!(tomorrow[0], due[0]);
+    }
+
+    #[test]
+    fn schedules_survive_a_save_and_load() {
+        let mut deck = Deck::parse("broken\n8 7=x\n");
+        assert!(deck.schedules.is_empty());
+        let card = Card { bits: Bits::Eight, conversion: Conversion::DecimalToBinary, raw: 165 };
+        deck.review(&card, 4, 20_743);
+        assert_eq!(deck.to_text(), "8-d2b 165=1 1 2.50 20744 20743\n");
+        assert_eq!(Deck::parse(&deck.to_text()).schedules, deck.schedules);
+    }
+}
@@ -0,0 +1,286 @@
+use crate::binary_numbers::PuzzleConfig;
+use crate::keybinds;
+use crate::main_screen_widget::WidgetRef;
+use crate::puzzle::{Choices, Puzzle, Round, render_centered_prompt, step_selection};
+use crossterm::event::KeyEvent;
+use rand::Rng;
+use rand::prelude::SliceRandom;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Color, Line, Style, Stylize};
+use ratatui::text::Span;
+
+const EXPONENT_BITS: u32 = 5;
+const MANTISSA_BITS: u32 = 10;
+const EXPONENT_MAX: u16 = (1 << EXPONENT_BITS) - 1;
+const MANTISSA_MASK: u16 = (1 << MANTISSA_BITS) - 1;
+const BIAS: i32 = 15;
+
+const SIGN_COLOR: Color = Color::LightMagenta;
+const EXPONENT_COLOR: Color = Color::LightYellow;
+const MANTISSA_COLOR: Color = Color::LightBlue;
+
+/// The sign, exponent and mantissa fields of an IEEE 754 binary16 pattern
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct HalfFields {
+    sign: bool,
+    exponent: u16,
+    mantissa: u16,
+}
+
+impl HalfFields {
+    const fn from_bits(bits: u16) -> Self {
+        Self {
+            sign: bits >> 15 == 1,
+            exponent: (bits >> MANTISSA_BITS) & EXPONENT_MAX,
+            mantissa: bits & MANTISSA_MASK,
+        }
+    }
+
+    /// The fraction `1.mantissa`, or `0.mantissa` for subnormals
+    fn significand(self, implicit_one: bool) -> f32 {
+        let fraction = f32::from(self.mantissa) / f32::from(1u16 << MANTISSA_BITS);
+        if implicit_one { 1.0 + fraction } else { fraction }
+    }
+
+    fn signed(self, magnitude: f32) -> f32 {
+        if self.sign { -magnitude } else { magnitude }
+    }
+
+    fn decode(self) -> f32 {
+        match self.exponent {
+            // subnormals have the exponent of the smallest normal number and no implicit one
+            0 => self.signed(self.significand(false) * 2f32.powi(1 - BIAS)),
+            EXPONENT_MAX if self.mantissa == 0 => self.signed(f32::INFINITY),
+            EXPONENT_MAX => f32::NAN,
+            e => self.signed(self.significand(true) * 2f32.powi(i32::from(e) - BIAS)),
+        }
+    }
+
+    /// Values that come out of the typical slips while decoding the fields
+    fn misreadings(self) -> Vec<f32> {
+        let value = self.decode();
+        let exponent = i32::from(self.exponent);
+        let mut misreadings = vec![
+            -value,
+            // bias off by one
+            value * 2.0,
+            value / 2.0,
+            // implicit one added to a subnormal, or forgotten for a normal number
+            self.signed(
+                self.significand(self.exponent == 0) * 2f32.powi((exponent - BIAS).max(1 - BIAS)),
+            ),
+            // exponent read without the bias
+            self.signed(self.significand(true) * 2f32.powi(exponent)),
+        ];
+        if self.exponent == EXPONENT_MAX {
+            // infinity and NaN only differ in the mantissa
+            misreadings.extend([f32::NAN, self.signed(f32::INFINITY), self.signed(65504.0)]);
+        }
+        if self.exponent == 0 && self.mantissa == 0 {
+            misreadings.extend([self.signed(2f32.powi(-BIAS)), self.signed(2f32.powi(-24))]);
+        }
+        misreadings
+    }
+}
+
+/// Decoded value as offered to the player. The sign of zero and infinity is always shown,
+/// very small and very large values are written in scientific notation.
+fn format_half(value: f32) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() || value == 0.0 {
+        let sign = if value.is_sign_negative() { '-' } else { '+' };
+        let magnitude = if value.is_infinite() { "inf" } else { "0" };
+        format!("{sign}{magnitude}")
+    } else if (1e-3..1e5).contains(&value.abs()) {
+        value.to_string()
+    } else {
+        format!("{value:e}")
+    }
+}
+
+/// Shows a 16 bit pattern split into its sign, exponent and mantissa fields,
+/// the player picks the decoded half-precision value.
+pub struct HalfFloatPuzzle {
+    fields: HalfFields,
+    suggestions: Vec<String>,
+    answer: usize,
+    selected_suggestion: Option<usize>,
+    round: Round,
+}
+
+impl Puzzle for HalfFloatPuzzle {
+    fn new(config: &PuzzleConfig, streak: u32, rng: &mut impl Rng) -> Self {
+        let fields = random_fields(rng);
+
+        let answer_label = format_half(fields.decode());
+        let mut misreadings: Vec<String> =
+            fields.misreadings().into_iter().map(format_half).collect();
+        misreadings.shuffle(rng);
+
+        let mut suggestions = vec![answer_label.clone()];
+        for candidate in misreadings {
+            if suggestions.len() < config.bits.suggestion_count()
+                && !suggestions.contains(&candidate)
+            {
+                suggestions.push(candidate);
+            }
+        }
+        while suggestions.len() < config.bits.suggestion_count() {
+            let candidate = format_half(random_fields(rng).decode());
+            if !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+            }
+        }
+        suggestions.shuffle(rng);
+        let answer = suggestions.iter().position(|s| *s == answer_label).unwrap_or(0);
+
+        Self {
+            fields,
+            suggestions,
+            answer,
+            selected_suggestion: Some(0),
+            round: Round::new(streak),
+        }
+    }
+
+    fn round(&self) -> &Round {
+        &self.round
+    }
+
+    fn round_mut(&mut self) -> &mut Round {
+        &mut self.round
+    }
+
+    fn handle_answer_input(&mut self, input: KeyEvent) {
+        let indices: Vec<usize> = (0..self.suggestions.len()).collect();
+        match input {
+            x if keybinds::is_right(x) || keybinds::is_down(x) => {
+                self.selected_suggestion = step_selection(&indices, self.selected_suggestion, true);
+            },
+            x if keybinds::is_left(x) || keybinds::is_up(x) => {
+                self.selected_suggestion =
+                    step_selection(&indices, self.selected_suggestion, false);
+            },
+            x if keybinds::is_select(x) => {
+                if let Some(selected) = self.selected_suggestion {
+                    self.round.resolve(selected == self.answer);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn answer_height(&self, width: u16) -> u16 {
+        self.choices().height(width)
+    }
+
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer) {
+        render_centered_prompt(self.prompt_lines(), area, buf);
+    }
+
+    fn render_answer(&self, area: Rect, buf: &mut Buffer) {
+        self.choices().render_ref(area, buf);
+    }
+
+    fn answer_hint(&self, width: u16) -> (&'static str, &'static str) {
+        self.choices().hint(width)
+    }
+}
+
+impl HalfFloatPuzzle {
+    /// The three fields in their own colors, with a legend underneath for telling them
+    /// apart without color
+    fn prompt_lines(&self) -> Vec<Line<'static>> {
+        let field = |text: String, color: Color| Span::styled(text, Style::default().fg(color));
+        let legend =
+            |text: &'static str, color: Color| Span::styled(text, Style::default().fg(color).dim());
+        let exponent_width = EXPONENT_BITS as usize;
+        let mantissa_width = MANTISSA_BITS as usize;
+        vec![
+            Line::from(vec![
+                field(u8::from(self.fields.sign).to_string(), SIGN_COLOR),
+                Span::raw(" "),
+                field(format!("{:0exponent_width$b}", self.fields.exponent), EXPONENT_COLOR),
+                Span::raw(" "),
+                field(format!("{:0mantissa_width$b}", self.fields.mantissa), MANTISSA_COLOR),
+            ]),
+            Line::from(vec![
+                legend("±", SIGN_COLOR),
+                Span::raw(" "),
+                legend("exp  ", EXPONENT_COLOR),
+                Span::raw(" "),
+                legend("mantissa  ", MANTISSA_COLOR),
+            ]),
+            Line::from(Span::styled(
+                format!("exponent bias {BIAS}"),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ]
+    }
+
+    fn choices(&self) -> Choices {
+        Choices {
+            labels: self.suggestions.clone(),
+            selected: self.selected_suggestion,
+            correct: self.answer,
+            result: self.round.guess_result,
+        }
+    }
+}
+
+/// A random pattern that is mostly a normal number, but regularly one of the special cases.
+/// Only the top mantissa bits are used, so the value can be worked out within a round.
+fn random_fields(rng: &mut impl Rng) -> HalfFields {
+    let sign = u16::from(rng.random_bool(0.5)) << 15;
+    let mantissa = rng.random_range(0..16u16) << (MANTISSA_BITS - 4);
+    let (exponent, mantissa) = match rng.random_range(0..10) {
+        0 => (0, 0),                                                 // zero
+        1 | 2 => (0, mantissa.max(1 << (MANTISSA_BITS - 4))),        // subnormal
+        3 => (EXPONENT_MAX, 0),                                      // infinity
+        4 => (EXPONENT_MAX, mantissa.max(1 << (MANTISSA_BITS - 1))), // quiet NaN
+        _ => (rng.random_range(1..EXPONENT_MAX), mantissa),
+    };
+    HalfFields::from_bits(sign | exponent << MANTISSA_BITS | mantissa)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::NumberMode;
+    use crate::binary_numbers::{Bits, PuzzleKind};
+    use crate::puzzle::{assert_unique, seeded_puzzles};
+
+    fn decode(bits: u16) -> String {
+        format_half(HalfFields::from_bits(bits).decode())
+    }
+
+    #[test]
+    fn decodes_normals_subnormals_and_special_values() {
+        assert_eq!(decode(0x3C00), "1");
+        assert_eq!(decode(0xC000), "-2");
+        assert_eq!(decode(0x3E00), "1.5");
+        assert_eq!(decode(0x7BFF), "65504");
+        assert_eq!(decode(0x0400), "6.1035156e-5"); // smallest normal
+        assert_eq!(decode(0x0001), "5.9604645e-8"); // smallest subnormal
+        assert_eq!(decode(0x0200), "3.0517578e-5");
+        assert_eq!(decode(0x0000), "+0");
+        assert_eq!(decode(0x8000), "-0");
+        assert_eq!(decode(0x7C00), "+inf");
+        assert_eq!(decode(0xFC00), "-inf");
+        assert_eq!(decode(0x7E00), "NaN");
+    }
+
+    #[test]
+    fn puzzle_offers_unique_suggestions_including_the_value() {
+        let config =
+            PuzzleConfig::new(Bits::Sixteen, NumberMode::Unsigned).with_kind(PuzzleKind::HalfFloat);
+        assert_eq!(config.high_score_key(), "16-half");
+        for p in seeded_puzzles::<HalfFloatPuzzle>(&config) {
+            assert_eq!(p.suggestions.len(), Bits::Sixteen.suggestion_count());
+            assert_eq!(p.suggestions[p.answer], format_half(p.fields.decode()));
+            assert_unique(&p.suggestions);
+        }
+    }
+}
@@ -20,6 +20,10 @@ pub const fn is_select(key: KeyEvent) -> bool {
     matches!(key.code, KeyCode::Enter)
 }
 
+pub const fn is_delete(key: KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Backspace | KeyCode::Delete)
+}
+
 pub const fn is_exit(key: KeyEvent) -> bool {
     matches!(key.code, KeyCode::Esc | KeyCode::Char('q' | 'Q'))
 }
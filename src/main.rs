@@ -1,13 +1,102 @@
 mod app;
+mod arithmetic;
+mod bcd;
 mod binary_numbers;
+mod bitwise;
+mod daily;
+mod fixed_point;
+mod flashcards;
+mod float;
 mod keybinds;
 mod main_screen_widget;
+mod mixed;
+mod puzzle;
+mod storage;
 mod utils;
+mod weakness;
+
+use color_eyre::eyre::{bail, eyre};
+use std::path::PathBuf;
+
+/// Command line options
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    /// `--seed <n>`: every game draws the same numbers, e.g. to race a friend or replay a bug
+    seed: Option<u64>,
+    /// `--scores <file>`: the high score file, the weak spots and study deck are kept next to it
+    scores: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> color_eyre::Result<Self> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            match name.as_str() {
+                "--seed" => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .ok_or_else(|| eyre!("--seed needs a number"))?;
+                    let seed =
+                        value.parse().map_err(|_| eyre!("--seed needs a number, got {value:?}"))?;
+                    parsed.seed = Some(seed);
+                },
+                "--scores" => {
+                    let value = inline_value
+                        .or_else(|| args.next())
+                        .ok_or_else(|| eyre!("--scores needs a file"))?;
+                    parsed.scores = Some(PathBuf::from(value));
+                },
+                _ => bail!(
+                    "unknown argument {name:?}, usage: binbreak [--seed <n>] [--scores <file>]"
+                ),
+            }
+        }
+        Ok(parsed)
+    }
+}
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let args = Args::parse(std::env::args().skip(1))?;
+    storage::set_high_score_path(args.scores);
+    storage::migrate_from_working_dir(&[
+        storage::HIGH_SCORES,
+        weakness::Weaknesses::FILE,
+        flashcards::Deck::FILE,
+    ]);
     let mut terminal = ratatui::init();
-    let result = app::run_app(&mut terminal);
+    let result = app::run_app(&mut terminal, args.seed);
     ratatui::restore();
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> color_eyre::Result<Args> {
+        Args::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn seed_is_read_from_either_form_of_the_flag() {
+        assert_eq!(parse(&[]).ok(), Some(Args::default()));
+        assert_eq!(parse(&["--seed", "42"]).ok(), Some(Args { seed: Some(42), scores: None }));
+        assert_eq!(parse(&["--seed=7"]).ok(), Some(Args { seed: Some(7), scores: None }));
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--seed", "-1"]).is_err());
+        assert!(parse(&["--speed", "42"]).is_err());
+    }
+
+    #[test]
+    fn scores_can_be_kept_anywhere() {
+        let scores = Some(PathBuf::from("/tmp/scores.txt"));
+        assert_eq!(parse(&["--scores", "/tmp/scores.txt"]).ok(), Some(Args { seed: None, scores }));
+        assert!(parse(&["--seed", "1", "--scores"]).is_err());
+    }
+}
@@ -10,4 +10,6 @@ pub trait MainScreenWidget: WidgetRef {
     fn run(&mut self, dt: f64) -> ();
     fn handle_input(&mut self, input: KeyEvent) -> ();
     fn is_exit_intended(&self) -> bool;
+    /// Whether the screen is animating and needs continuous frame updates
+    fn is_active(&self) -> bool;
 }
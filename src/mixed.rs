@@ -0,0 +1,70 @@
+use crate::app::NumberMode;
+use crate::binary_numbers::{Bits, GameMode, PuzzleConfig};
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+/// Correct answers in a row it takes for the likeliest width to move one preset wider
+const STREAK_PER_WIDTH: u32 = 2;
+
+/// Widths next to the likeliest one that can still show up, at lower odds the further away
+const WIDTH_SPREAD: usize = 3;
+
+/// The width of the first round; each round draws its own width and number mode
+pub fn config(seed: Option<u64>) -> PuzzleConfig {
+    PuzzleConfig::new(Bits::Four, NumberMode::Unsigned)
+        .with_seed(seed)
+        .with_game_mode(GameMode::Mixed)
+}
+
+/// Draw the width and number mode of the next round. Narrow unsigned words come first,
+/// the longer the streak the wider the words and the more often they are signed.
+pub fn pick(streak: u32, rng: &mut impl Rng) -> (Bits, NumberMode) {
+    let likeliest = (streak / STREAK_PER_WIDTH) as usize;
+    let likeliest = likeliest.min(Bits::PRESETS.len() - 1);
+    let bits = (0..Bits::PRESETS.len())
+        .collect::<Vec<_>>()
+        .choose_weighted(rng, |&i| (WIDTH_SPREAD + 1).saturating_sub(i.abs_diff(likeliest)))
+        .map_or(Bits::Four, |&i| Bits::PRESETS[i].clone());
+
+    let signed_weight = (streak / 4).min(3);
+    let number_mode = NumberMode::ALL
+        .choose_weighted(rng, |&mode| if mode.is_signed() { signed_weight } else { 4 })
+        .map_or(NumberMode::Unsigned, |&mode| mode);
+    (bits, number_mode)
+}
+
+/// Points multiplier of a round: one more for every second preset of width, one more if signed
+pub fn difficulty(bits: &Bits, number_mode: NumberMode) -> u32 {
+    let rank = Bits::PRESETS.iter().position(|preset| preset == bits).unwrap_or(0);
+    #[allow(clippy::cast_possible_truncation)]
+    let width = 1 + rank as u32 / 2;
+    width + u32::from(number_mode.is_signed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn wider_and_signed_words_come_with_the_streak() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        for _ in 0..50 {
+            let (bits, number_mode) = pick(0, &mut rng);
+            assert!(bits.to_int() <= 8, "{bits:?}");
+            assert_eq!(number_mode, NumberMode::Unsigned);
+        }
+        let late: Vec<_> = (0..200).map(|_| pick(30, &mut rng)).collect();
+        assert!(late.iter().all(|(bits, _)| bits.to_int() >= 12));
+        assert!(late.iter().any(|(_, number_mode)| number_mode.is_signed()));
+    }
+
+    #[test]
+    fn harder_rounds_are_worth_more() {
+        assert_eq!(difficulty(&Bits::Four, NumberMode::Unsigned), 1);
+        assert_eq!(difficulty(&Bits::Eight, NumberMode::Unsigned), 3);
+        assert_eq!(difficulty(&Bits::Eight, NumberMode::Signed), 4);
+        assert_eq!(difficulty(&Bits::ThirtyTwo, NumberMode::Excess), 6);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::binary_numbers::PuzzleConfig;
+use crate::main_screen_widget::WidgetRef;
+use crate::utils::{When, center};
+use crate::weakness::Attempt;
+use crossterm::event::KeyEvent;
+use rand::Rng;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
+use ratatui::prelude::Alignment::Center;
+use ratatui::prelude::{Color, Line, Style, Stylize, Widget};
+use ratatui::text::Span;
+use ratatui::widgets::{Block, BorderType, Paragraph};
+
+/// A question the player answers within one round of `BinaryNumbersGame`.
+/// The game owns score, lives and streak, the puzzle owns its prompt and answer widget.
+pub trait Puzzle {
+    /// A fresh question, drawing every random choice from `rng` so a seeded game can be replayed
+    fn new(config: &PuzzleConfig, streak: u32, rng: &mut impl Rng) -> Self
+    where
+        Self: Sized;
+    fn round(&self) -> &Round;
+    fn round_mut(&mut self) -> &mut Round;
+    /// Handle a key press while the round is open, resolving the round once an answer is given
+    fn handle_answer_input(&mut self, input: KeyEvent);
+    /// Height of the answer widget for the available width
+    fn answer_height(&self, width: u16) -> u16;
+    fn render_prompt(&self, area: Rect, buf: &mut Buffer);
+    fn render_answer(&self, area: Rect, buf: &mut Buffer);
+    /// Hotkey and description for giving an answer, shown before the common hotkeys
+    fn answer_hint(&self, width: u16) -> (&'static str, &'static str);
+    /// Help for practice games, revealed one at a time and each giving away a bit more
+    fn hints(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// The pattern asked about and how the answer went, once the round is over.
+    /// Puzzles that report it learn the player's weak spots and ask them more often.
+    fn attempt(&self) -> Option<Attempt> {
+        None
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum GuessResult {
+    Correct,
+    Incorrect,
+    Timeout,
+}
+
+/// Border color of the answer widget: the outcome, or cyan while the round is open
+pub const fn result_color(result: Option<GuessResult>) -> Color {
+    match result {
+        Some(GuessResult::Correct) => Color::Green,
+        Some(GuessResult::Incorrect) => Color::Red,
+        Some(GuessResult::Timeout) => Color::Yellow,
+        None => Color::LightCyan,
+    }
+}
+
+/// Countdown and outcome of a single round, shared by every kind of puzzle
+pub struct Round {
+    pub time_total: f64,
+    pub time_left: f64,
+    pub guess_result: Option<GuessResult>,
+    pub last_points_awarded: u32,
+    pub hints_shown: usize,
+    skip_first_dt: bool, // Skip first dt to prevent timer jump when starting new puzzle
+}
+
+impl Round {
+    pub fn new(streak: u32) -> Self {
+        // Calculate time based on difficulty
+        Self::with_time(10.0 - (f64::from(streak.min(8)) * 0.5))
+    }
+
+    pub const fn with_time(time_total: f64) -> Self {
+        Self {
+            time_total,
+            time_left: time_total,
+            guess_result: None,
+            last_points_awarded: 0,
+            hints_shown: 0,
+            skip_first_dt: true,
+        }
+    }
+
+    pub const fn resolve(&mut self, correct: bool) {
+        self.guess_result = if correct {
+            Some(GuessResult::Correct)
+        } else {
+            Some(GuessResult::Incorrect)
+        };
+    }
+
+    pub fn run(&mut self, dt: f64) {
+        if self.skip_first_dt {
+            self.skip_first_dt = false;
+            return;
+        }
+        if self.guess_result.is_some() {
+            return;
+        }
+        self.time_left -= dt;
+        if self.time_left <= 0.0 {
+            self.guess_result = Some(GuessResult::Timeout);
+        }
+    }
+}
+
+/// The suggestion after or before the selected one, wrapping around at both ends.
+/// Without a selection, moving forward selects the first suggestion.
+pub fn step_selection<T: Copy + PartialEq>(
+    suggestions: &[T],
+    selected: Option<T>,
+    forward: bool,
+) -> Option<T> {
+    let Some(selected) = selected else {
+        return if forward { suggestions.first().copied() } else { None };
+    };
+    let Some(index) = suggestions.iter().position(|&s| s == selected) else {
+        return Some(selected);
+    };
+    let next_index = if forward {
+        (index + 1) % suggestions.len()
+    } else if index == 0 {
+        suggestions.len() - 1
+    } else {
+        index - 1
+    };
+    Some(suggestions[next_index])
+}
+
+/// The prompt of a puzzle in a double border, its lines centered both ways.
+/// Lines of the same width, like the operands of a bit operation, keep their bits aligned.
+pub fn render_centered_prompt(lines: Vec<Line<'_>>, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered()
+        .border_type(BorderType::Double)
+        .border_style(Style::default().dark_gray());
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    #[allow(clippy::cast_possible_truncation)]
+    let [rows] =
+        Layout::vertical([Constraint::Length(lines.len() as u16)]).flex(Flex::Center).areas(inner);
+    Paragraph::new(lines).alignment(Center).render(rows, buf);
+}
+
+/// Puzzles of the config drawn from 50 seeds, so a failing check can be replayed
+#[cfg(test)]
+pub fn seeded_puzzles<P: Puzzle>(config: &PuzzleConfig) -> impl Iterator<Item = P> + '_ {
+    use rand::SeedableRng;
+    (0..50).map(|seed| P::new(config, 0, &mut rand_chacha::ChaCha8Rng::seed_from_u64(seed)))
+}
+
+/// Fails if any suggestion is offered twice
+#[cfg(test)]
+pub fn assert_unique<T: Ord + std::fmt::Debug>(suggestions: &[T]) {
+    let mut sorted: Vec<&T> = suggestions.iter().collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(sorted.len(), suggestions.len(), "{suggestions:?}");
+}
+
+/// Multiple choice answers, side by side or as a vertical list when they are too wide
+pub struct Choices {
+    pub labels: Vec<String>,
+    pub selected: Option<usize>,
+    pub correct: usize,
+    pub result: Option<GuessResult>,
+}
+
+impl Choices {
+    // Long answers (e.g. 16 bit patterns) don't fit side by side, so list them vertically.
+    // The columns are equally wide, so the widest answer decides.
+    pub fn fit_horizontally(&self, width: u16) -> bool {
+        let widest = self.labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        (widest + 4) * self.labels.len() <= usize::from(width)
+    }
+
+    pub fn height(&self, width: u16) -> u16 {
+        #[allow(clippy::cast_possible_truncation)]
+        if self.fit_horizontally(width) {
+            3
+        } else {
+            self.labels.len() as u16 + 2
+        }
+    }
+
+    pub fn hint(&self, width: u16) -> (&'static str, &'static str) {
+        if self.fit_horizontally(width) {
+            ("Left Right", "select  ")
+        } else {
+            ("Up Down", "select  ")
+        }
+    }
+
+    fn render_horizontal(&self, area: Rect, buf: &mut Buffer) {
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Min(6); self.labels.len()])
+            .split(area);
+
+        for (i, label) in self.labels.iter().enumerate() {
+            let item_is_selected = self.selected == Some(i);
+            let show_correct_number = self.result.is_some();
+            let area = layout[i];
+
+            let border_type = if item_is_selected {
+                BorderType::Double
+            } else {
+                BorderType::Plain
+            };
+            let border_color = if item_is_selected {
+                result_color(self.result)
+            } else {
+                Color::DarkGray
+            };
+
+            Block::bordered().border_type(border_type).fg(border_color).render(area, buf);
+
+            #[allow(clippy::cast_possible_truncation)]
+            Paragraph::new(label.clone())
+                .white()
+                .when(show_correct_number && i == self.correct, |p| p.light_green().underlined())
+                .alignment(Center)
+                .render(center(area, Constraint::Length(label.chars().count() as u16)), buf);
+        }
+    }
+
+    fn render_stacked(&self, area: Rect, buf: &mut Buffer) {
+        Block::bordered().dark_gray().render(area, buf);
+
+        let show_correct_number = self.result.is_some();
+        let lines: Vec<Line> = self
+            .labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let item_is_selected = self.selected == Some(i);
+                let marker = if item_is_selected { '»' } else { ' ' };
+                let color = if item_is_selected {
+                    result_color(self.result)
+                } else {
+                    Color::White
+                };
+                Line::from(Span::styled(format!("{marker} {label}"), Style::default().fg(color)))
+                    .when(show_correct_number && i == self.correct, |l| {
+                        l.light_green().underlined()
+                    })
+            })
+            .collect();
+
+        #[allow(clippy::cast_possible_truncation)]
+        let widest = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let inner = Block::bordered().inner(area);
+        let [column] =
+            Layout::horizontal([Constraint::Length(widest)]).flex(Flex::Center).areas(inner);
+        Paragraph::new(lines).render(column, buf);
+    }
+}
+
+impl WidgetRef for Choices {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        if self.fit_horizontally(area.width) {
+            self.render_horizontal(area, buf);
+        } else {
+            self.render_stacked(area, buf);
+        }
+    }
+}
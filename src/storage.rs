@@ -0,0 +1,124 @@
+use crate::utils::data_dir;
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The high score file, the weak spots and the study deck are kept next to it
+pub const HIGH_SCORES: &str = "binbreak_highscores.txt";
+
+/// Environment variable with the path of the high score file, for when `--scores` isn't given
+pub const SCORES_VAR: &str = "BINBREAK_SCORES";
+
+thread_local! {
+    /// From `--scores`, or a file of their own for tests, which never touch the player's scores
+    static HIGH_SCORE_PATH: RefCell<Option<PathBuf>> = RefCell::new(
+        cfg!(test).then(|| std::env::temp_dir().join("binbreak-tests").join(HIGH_SCORES)),
+    );
+}
+
+/// Use the given high score file instead of the one in the data directory
+pub fn set_high_score_path(path: Option<PathBuf>) {
+    HIGH_SCORE_PATH.with_borrow_mut(|high_score_path| *high_score_path = path);
+}
+
+/// The path set with `--scores`, then the one in `BINBREAK_SCORES`
+fn chosen_path() -> Option<PathBuf> {
+    let from_env = || std::env::var_os(SCORES_VAR).filter(|path| !path.is_empty());
+    HIGH_SCORE_PATH.with_borrow(Clone::clone).or_else(|| from_env().map(PathBuf::from))
+}
+
+/// `binbreak/` in the data directory of the platform, or the working directory without one
+fn default_dir() -> PathBuf {
+    data_dir().map_or_else(PathBuf::new, |dir| dir.join("binbreak"))
+}
+
+pub fn high_scores() -> PathBuf {
+    chosen_path().unwrap_or_else(|| default_dir().join(HIGH_SCORES))
+}
+
+/// A file kept in the same directory as the high scores
+pub fn next_to_high_scores(file: &str) -> PathBuf {
+    high_scores().with_file_name(file)
+}
+
+/// Write a file next to the high scores, creating their directory first
+pub fn write(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Earlier versions kept their files in the working directory. Move them to the data
+/// directory, unless another path was chosen or the data directory has them already.
+pub fn migrate_from_working_dir(files: &[&str]) {
+    if chosen_path().is_none() {
+        move_files(Path::new(""), &default_dir(), files);
+    }
+}
+
+fn move_files(from: &Path, to: &Path, files: &[&str]) {
+    for file in files {
+        let (old, new) = (from.join(file), to.join(file));
+        if old == new || !old.is_file() || new.exists() {
+            continue;
+        }
+        // a rename can't cross file systems, a copy can
+        let moved = fs::create_dir_all(to).and_then(|()| {
+            fs::rename(&old, &new)
+                .or_else(|_| fs::copy(&old, &new).and_then(|_| fs::remove_file(&old)))
+        });
+        if moved.is_err() {
+            let _ = fs::remove_file(&new);
+        }
+    }
+}
+
+/// Runs a test with its high scores in a fresh directory, removed afterwards
+#[cfg(test)]
+pub fn with_files_in_temp_dir<F: FnOnce(&Path)>(f: F) {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static DIRS: AtomicUsize = AtomicUsize::new(0);
+    let test = DIRS.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("binbreak-{}-{test}", std::process::id()));
+    let previous = HIGH_SCORE_PATH.with_borrow(Clone::clone);
+    set_high_score_path(Some(dir.join(HIGH_SCORES)));
+    f(&dir);
+    set_high_score_path(previous);
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_follow_the_high_scores() {
+        with_files_in_temp_dir(|dir| {
+            assert_eq!(high_scores(), dir.join(HIGH_SCORES));
+            assert_eq!(next_to_high_scores("deck.txt"), dir.join("deck.txt"));
+            set_high_score_path(Some(PathBuf::from("scores.txt")));
+            assert_eq!(next_to_high_scores("deck.txt"), PathBuf::from("deck.txt"));
+        });
+    }
+
+    #[test]
+    fn old_files_move_unless_the_new_place_has_them() {
+        with_files_in_temp_dir(|dir| {
+            let (old, new) = (dir.join("old"), dir.join("data").join("binbreak"));
+            for (file, contents) in [("a.txt", "old a"), ("b.txt", "old b")] {
+                assert!(write(&old.join(file), contents).is_ok());
+            }
+            assert!(write(&new.join("b.txt"), "new b").is_ok());
+            move_files(&old, &new, &["a.txt", "b.txt", "c.txt"]);
+
+            let read = |path: PathBuf| fs::read_to_string(path).ok();
+            assert_eq!(read(new.join("a.txt")).as_deref(), Some("old a"));
+            assert_eq!(read(new.join("b.txt")).as_deref(), Some("new b"));
+            assert!(!old.join("a.txt").exists());
+            assert!(old.join("b.txt").exists());
+            assert!(!new.join("c.txt").exists());
+        });
+    }
+}
@@ -1,5 +1,6 @@
 use ratatui::layout::Flex;
 use ratatui::prelude::*;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 /// Type alias for the color function used in procedural animations
@@ -179,6 +180,32 @@ impl ProceduralAnimationWidget {
     }
 }
 
+/// Where the platform keeps per-user settings: `$XDG_CONFIG_HOME` or `~/.config` on Linux,
+/// `~/Library/Application Support` on macOS and `%APPDATA%` on Windows
+pub fn config_dir() -> Option<PathBuf> {
+    user_dir("XDG_CONFIG_HOME", &[".config"])
+}
+
+/// Where the platform keeps per-user data like scores: `$XDG_DATA_HOME` or `~/.local/share`
+/// on Linux, the same directory as the settings on macOS and Windows
+pub fn data_dir() -> Option<PathBuf> {
+    user_dir("XDG_DATA_HOME", &[".local", "share"])
+}
+
+/// The XDG directory from the given variable or below the home directory on Linux
+fn user_dir(xdg_var: &str, below_home: &[&str]) -> Option<PathBuf> {
+    let env_dir = |name| std::env::var_os(name).map(PathBuf::from).filter(|dir| dir.is_absolute());
+    if cfg!(windows) {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        let home =
+            || env_dir("HOME").map(|home| below_home.iter().fold(home, |dir, part| dir.join(part)));
+        env_dir(xdg_var).or_else(home)
+    }
+}
+
 pub fn center(area: Rect, horizontal: Constraint) -> Rect {
     let [area] = Layout::horizontal([horizontal]).flex(Flex::Center).areas(area);
 
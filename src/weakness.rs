@@ -0,0 +1,213 @@
+use crate::storage;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+/// Percent of puzzles that ask for a weak value again, and that set a weak bit
+const WEAK_VALUE_PERCENT: u32 = 40;
+const WEAK_BIT_PERCENT: u32 = 20;
+
+/// Highest weight of a value or bit, so a long run of misses can still be made up for
+const MAX_WEIGHT: u32 = 20;
+
+/// How a round went, as far as the weakness model cares
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Attempt {
+    /// The bit pattern that was asked about
+    pub raw: u64,
+    /// Bits the answer had wrong, none if it was right or skipped
+    pub wrong_bits: u64,
+    pub correct: bool,
+    /// Took more than half of the round
+    pub slow: bool,
+}
+
+/// Values and bit positions of one width and number mode that the player keeps missing.
+/// Weights grow with every miss or slow answer and shrink with every quick correct one.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct WeakSpots {
+    values: BTreeMap<u64, u32>,
+    /// Misses by bit position, 0 is the least significant bit
+    bits: BTreeMap<u32, u32>,
+}
+
+impl WeakSpots {
+    fn record(&mut self, attempt: Attempt) {
+        let weight = self.values.entry(attempt.raw).or_default();
+        if !attempt.correct {
+            *weight = (*weight + 2).min(MAX_WEIGHT);
+        } else if attempt.slow {
+            *weight = (*weight + 1).min(MAX_WEIGHT);
+        } else {
+            *weight = weight.saturating_sub(1);
+        }
+        if *weight == 0 {
+            self.values.remove(&attempt.raw);
+        }
+
+        for bit in 0..u64::BITS {
+            if attempt.wrong_bits >> bit & 1 == 1 {
+                let misses = self.bits.entry(bit).or_default();
+                *misses = (*misses + 1).min(MAX_WEIGHT);
+            } else if attempt.correct
+                && attempt.raw >> bit & 1 == 1
+                && let Some(misses) = self.bits.get_mut(&bit)
+            {
+                // getting a set bit right makes up for an earlier miss
+                *misses -= 1;
+                if *misses == 0 {
+                    self.bits.remove(&bit);
+                }
+            }
+        }
+    }
+
+    /// A pattern of at most `mask`, more often one of the weak values or with a weak bit set
+    pub fn draw(&self, mask: u64, rng: &mut impl Rng) -> u64 {
+        let roll = rng.random_range(0..100);
+        let weak_values: Vec<_> = self.values.iter().filter(|&(&raw, _)| raw <= mask).collect();
+        let weak_bits: Vec<_> = self.bits.iter().filter(|&(&bit, _)| 1 << bit <= mask).collect();
+        if roll < WEAK_VALUE_PERCENT
+            && let Ok(&(&raw, _)) = weak_values.choose_weighted(rng, |&(_, &weight)| weight)
+        {
+            return raw;
+        }
+        let raw = rng.random_range(0..=mask);
+        if roll < WEAK_VALUE_PERCENT + WEAK_BIT_PERCENT
+            && let Ok(&(&bit, _)) = weak_bits.choose_weighted(rng, |&(_, &misses)| misses)
+        {
+            return raw | 1 << bit;
+        }
+        raw
+    }
+}
+
+/// The weak spots of every width and number mode played, kept next to the high scores
+pub struct Weaknesses {
+    spots: BTreeMap<String, WeakSpots>,
+}
+
+impl Weaknesses {
+    pub const FILE: &'static str = "binbreak_weaknesses.txt";
+
+    pub fn load() -> Self {
+        Self::parse(&fs::read_to_string(Self::path()).unwrap_or_default())
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        storage::write(&Self::path(), &self.to_text())
+    }
+
+    pub fn path() -> PathBuf {
+        storage::next_to_high_scores(Self::FILE)
+    }
+
+    /// Lines like `8s value 165=3` and `8s bit 7=2`, anything else is skipped
+    fn parse(contents: &str) -> Self {
+        let mut spots: BTreeMap<String, WeakSpots> = BTreeMap::new();
+        for line in contents.lines() {
+            let Some((name, weight)) = line.split_once('=') else { continue };
+            let mut parts = name.split_whitespace();
+            let (Some(key), Some(kind), Some(at), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let Ok(weight) = weight.trim().parse::<u32>() else { continue };
+            let weight = weight.min(MAX_WEIGHT);
+            let weak = spots.entry(key.to_string()).or_default();
+            match (kind, at.parse::<u64>()) {
+                ("value", Ok(raw)) => {
+                    weak.values.insert(raw, weight);
+                },
+                ("bit", Ok(bit)) if bit < u64::from(u64::BITS) => {
+                    #[allow(clippy::cast_possible_truncation)]
+                    weak.bits.insert(bit as u32, weight);
+                },
+                _ => {},
+            }
+        }
+        Self { spots }
+    }
+
+    fn to_text(&self) -> String {
+        let mut data = String::new();
+        for (key, weak) in &self.spots {
+            for (raw, weight) in &weak.values {
+                let _ = writeln!(data, "{key} value {raw}={weight}");
+            }
+            for (bit, misses) in &weak.bits {
+                let _ = writeln!(data, "{key} bit {bit}={misses}");
+            }
+        }
+        data
+    }
+
+    /// The weak spots of one width and number mode, by their high score key like "8s"
+    pub fn spots(&self, key: &str) -> WeakSpots {
+        self.spots.get(key).cloned().unwrap_or_default()
+    }
+
+    pub fn record(&mut self, key: &str, attempt: Attempt) {
+        self.spots.entry(key.to_string()).or_default().record(attempt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn attempt(raw: u64, wrong_bits: u64, correct: bool) -> Attempt {
+        Attempt { raw, wrong_bits, correct, slow: false }
+    }
+
+    #[test]
+    fn misses_add_weight_and_quick_answers_take_it_away() {
+        let mut weak = WeakSpots::default();
+        weak.record(attempt(0b1011, 0b0100, false));
+        weak.record(Attempt { slow: true, ..attempt(0b0011, 0, true) });
+        assert_eq!(weak.values, BTreeMap::from([(0b0011, 1), (0b1011, 2)]));
+        assert_eq!(weak.bits, BTreeMap::from([(2, 1)]));
+
+        weak.record(attempt(0b1011, 0, true));
+        weak.record(attempt(0b0110, 0, true));
+        assert_eq!(weak.values, BTreeMap::from([(0b0011, 1), (0b1011, 1)]));
+        assert!(weak.bits.is_empty(), "bit 2 was set and right this time");
+    }
+
+    #[test]
+    fn weak_values_come_up_more_often() {
+        let mut weak = WeakSpots::default();
+        for _ in 0..100 {
+            weak.record(attempt(0xA5, 0b1000_0000, false));
+        }
+        assert_eq!(weak.values, BTreeMap::from([(0xA5, MAX_WEIGHT)]));
+        assert_eq!(weak.bits, BTreeMap::from([(7, MAX_WEIGHT)]));
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let repeats = (0..1000).filter(|_| weak.draw(0xFF, &mut rng) == 0xA5).count();
+        assert!(repeats > 300, "{repeats}");
+        // too wide for a nibble
+        assert!((0..100).all(|_| weak.draw(0xF, &mut rng) <= 0xF));
+    }
+
+    #[test]
+    fn spots_survive_a_save_and_load() {
+        let mut weaknesses = Weaknesses::parse(
+            "8s value 165=3\n8s bit 7=2\nbroken\n4u bit 99=1\n8u value 7=4294967295\n",
+        );
+        assert_eq!(weaknesses.spots("8u").values, BTreeMap::from([(7, MAX_WEIGHT)]));
+        assert_eq!(weaknesses.spots("8s").values, BTreeMap::from([(165, 3)]));
+        assert_eq!(weaknesses.spots("8s").bits, BTreeMap::from([(7, 2)]));
+        weaknesses.record("4u", attempt(3, 1, false));
+        assert_eq!(
+            weaknesses.to_text(),
+            "4u value 3=2\n4u bit 0=1\n8s value 165=3\n8s bit 7=2\n8u value 7=20\n"
+        );
+        assert_eq!(Weaknesses::parse(&weaknesses.to_text()).spots, weaknesses.spots);
+    }
+}